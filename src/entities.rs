@@ -14,6 +14,14 @@ pub mod Agent;
 #[allow(non_snake_case)]
 pub use Agent::*;
 
+// struct has the same name, see crate::Factorgraph
+#[allow(non_snake_case)]
+pub mod Factorgraph;
+
+// struct has the same name, see crate::Model
+#[allow(non_snake_case)]
+pub mod Model;
+
 #[allow(non_snake_case)]
 pub mod Distributions;
 #[allow(non_snake_case)]
@@ -38,3 +46,23 @@ pub use Variables::*;
 pub mod Factors;
 #[allow(non_snake_case)]
 pub use Factors::*;
+
+#[allow(non_snake_case)]
+pub mod Topology;
+#[allow(non_snake_case)]
+pub use Topology::*;
+
+#[allow(non_snake_case)]
+pub mod Visualize;
+#[allow(non_snake_case)]
+pub use Visualize::*;
+
+#[allow(non_snake_case)]
+pub mod FileDFG;
+#[allow(non_snake_case)]
+pub use FileDFG::*;
+
+#[allow(non_snake_case)]
+pub mod SyncDFG;
+#[allow(non_snake_case)]
+pub use SyncDFG::*;
@@ -1,11 +1,12 @@
 
 
 use crate::{
-    get_blob_entry, Utc, Uuid, parse_str_utc,
+    Utc, Uuid, parse_str_utc,
 };
 use chrono::{
     ParseError,
 };
+use serde::{Deserialize, Serialize};
 
 
 /// A `BlobEntry` is a small amount of structured data that holds contextual/reference information to find an actual blob. 
@@ -16,7 +17,7 @@ use chrono::{
 /// Notes:
 /// - `blobId`s should be unique within a blobstore and are immutable; or 
 ///   - if blobless, should have UUID("00000000-0000-0000-000000000000").
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct BlobEntry {
     /// Remotely assigned and globally unique identifier for the `BlobEntry` itself (not the `.blobId`).
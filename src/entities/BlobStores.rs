@@ -1,4 +1,5 @@
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::{
     NavAbilityClient
 };
@@ -1,10 +1,22 @@
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+};
 
 use crate::{
+    Uuid,
     Agent,
     NvaNode,
     Factorgraph,
+    VariableDFG,
+    FactorDFG,
+    FactorType,
+    AnyFactor,
+    ListFactorsFilter,
+    MeanMaxPPE,
+    BlobEntry,
+    Adjacency,
 };
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
@@ -33,4 +45,110 @@ pub struct NavAbilityDFG {
     pub fg: NvaNode<Factorgraph>,
     pub agent: NvaNode<Agent>,
     pub blobStores: HashMap<String, NavAbilityBlobStore>,
-}
\ No newline at end of file
+}
+
+
+/// In-memory factor graph, used to build and inspect a graph offline before pushing it to the API.
+/// Variables hold their PPEs and blob entries, same as with `NavAbilityDFG`, and ids are derived
+/// from `namespace` and `label` the same way so they match the remote graph.
+#[derive(Debug, Clone, Default)]
+pub struct LocalDFG {
+    pub namespace: Uuid,
+    pub label: String,
+    pub variables: BTreeMap<String, VariableDFG>,
+    pub factors: BTreeMap<String, FactorDFG<AnyFactor>>,
+}
+
+
+/// Common interface over factor graph backends such as `NavAbilityDFG` and `LocalDFG`, in the style
+/// of the AbstractDFG API of DistributedFactorGraphs.jl, so algorithms can be generic over the backend.
+#[allow(non_snake_case, async_fn_in_trait)]
+pub trait FactorGraphStore {
+    /// Label of the graph.
+    fn getGraphLabel(&self) -> String;
+    /// Description of the graph, empty if the backend does not keep one.
+    async fn getDescription(&self) -> Result<String, Box<dyn Error>> {
+        return Ok(String::new());
+    }
+
+    async fn getVariable(&self, label: &str) -> Result<Option<VariableDFG>, Box<dyn Error>>;
+    /// Add the variable node only, its `ppes`, `solverData` and `blobEntries` are not stored,
+    /// add those with `addPPE` and `addBlobEntry`.
+    async fn addVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>>;
+    /// Add several variables, backends may send them in a single request, see `post_add_variables`.
+    async fn addVariables(&mut self, variables: &[VariableDFG]) -> Result<Vec<Uuid>, Box<dyn Error>> {
+        let mut ids = Vec::new();
+        for variable in variables {
+            ids.push(self.addVariable(variable).await?);
+        }
+        return Ok(ids);
+    }
+    /// Update the variable node only, the stored `ppes`, `solverData` and `blobEntries` are kept
+    /// whatever `variable` holds, same as `addVariable`.
+    async fn updateVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>>;
+    /// Delete a variable and all factors connected to it.
+    async fn deleteVariable(&mut self, label: &str) -> Result<(), Box<dyn Error>>;
+    async fn listVariables(&self) -> Result<Vec<String>, Box<dyn Error>>;
+    /// Get a page of variables, backends may send a single request, see `post_get_variables`.
+    /// Labels not in the graph are left out and the order is not guaranteed.
+    async fn getVariables(&self, labels: &[String]) -> Result<Vec<VariableDFG>, Box<dyn Error>> {
+        let mut variables = Vec::new();
+        for label in labels {
+            if let Some(v) = self.getVariable(label).await? {
+                variables.push(v);
+            }
+        }
+        return Ok(variables);
+    }
+
+    async fn getFactor(&self, label: &str) -> Result<Option<FactorDFG<AnyFactor>>, Box<dyn Error>>;
+    /// Get a page of factors, same as `getVariables`.
+    async fn getFactors(&self, labels: &[String]) -> Result<Vec<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+        let mut factors = Vec::new();
+        for label in labels {
+            if let Some(f) = self.getFactor(label).await? {
+                factors.push(f);
+            }
+        }
+        return Ok(factors);
+    }
+    /// Add a factor, adding the same factor again is idempotent, see `post_add_factor`.
+    async fn addFactor<F: FactorType>(&mut self, factor: FactorDFG<F>) -> Result<Uuid, Box<dyn Error>>;
+    /// Add several factors, backends may send them in a single request, see `post_add_factors`.
+    async fn addFactors<F: FactorType>(&mut self, factors: Vec<FactorDFG<F>>) -> Result<Vec<Uuid>, Box<dyn Error>> {
+        let mut ids = Vec::new();
+        for factor in factors {
+            ids.push(self.addFactor(factor).await?);
+        }
+        return Ok(ids);
+    }
+    async fn updateFactor<F: FactorType>(&mut self, factor: &FactorDFG<F>) -> Result<Uuid, Box<dyn Error>>;
+    async fn deleteFactor(&mut self, label: &str) -> Result<(), Box<dyn Error>>;
+    async fn listFactors(&self, filter: &ListFactorsFilter) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// List all variable labels.
+    async fn ls(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.listVariables().await;
+    }
+    /// List all factor labels.
+    async fn lsf(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.listFactors(&ListFactorsFilter::default()).await;
+    }
+    /// Factors connected to a variable, or variables connected to a factor.
+    async fn getNeighbors(&self, label: &str) -> Result<Vec<String>, Box<dyn Error>>;
+    /// Snapshot of the connectivity of the whole graph, read again on every call and not updated when
+    /// the graph changes, see `services::shortest_path` and `services::is_connected`.
+    async fn getAdjacency(&self) -> Result<Adjacency, Box<dyn Error>>;
+
+    async fn getBlobEntry(&self, variableLabel: &str, label: &str) -> Result<Option<BlobEntry>, Box<dyn Error>>;
+    async fn addBlobEntry(&mut self, variableLabel: &str, entry: &BlobEntry) -> Result<Uuid, Box<dyn Error>>;
+    /// Delete a blob entry, the blob itself is kept.
+    async fn deleteBlobEntry(&mut self, variableLabel: &str, label: &str) -> Result<(), Box<dyn Error>>;
+
+    async fn getPPE(&self, variableLabel: &str, solveKey: &str) -> Result<Option<MeanMaxPPE>, Box<dyn Error>>;
+    async fn addPPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>>;
+    async fn updatePPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>>;
+    async fn deletePPE(&mut self, variableLabel: &str, solveKey: &str) -> Result<(), Box<dyn Error>>;
+    /// Solve keys of the PPEs on a variable.
+    async fn listPPEs(&self, variableLabel: &str) -> Result<Vec<String>, Box<dyn Error>>;
+}
@@ -1,52 +1,547 @@
 
 
+use std::error::Error;
+
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    ValidationError,
+    VariableType,
+    utils::{linalg, geometry},
+};
+
+
+/// Tolerance used when checking covariance matrices for symmetry and positive semi-definiteness.
+const COV_TOL: f64 = 1e-9;
+
+
+pub trait Distribution {
+    /// Convert to the IncrementalInference packed type, e.g. `PackedFullNormal`
+    fn to_packed(&self) -> PackedDistribution;
+    fn dim(&self) -> usize;
+
+    fn to_json(&self) -> String {
+        return self.to_packed().to_json();
+    }
+}
+
 
-pub trait Distribution<'a>: 'a {
-    fn new(params: Vec<&'a [f64]>) -> Self;
-    fn to_json(&self) -> String;
+fn validation_error(
+    details: String
+) -> Box<dyn Error> {
+    return Box::new(ValidationError { details });
 }
 
 
 /// Multidimensional normal distribution specified by means and a covariance matrix.
+/// The covariance is stored row-major, i.e. `cov[i*n + j]` for dimension `n`.  Only the
+/// validating constructors create a `FullNormal`, read the values with `mu` and `cov`.
 #[allow(non_snake_case)]
-#[derive(Debug, Clone, Default)]
-pub struct FullNormal<'a> {
-    pub mu: &'a [f64],
-    pub cov: &'a [f64],
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullNormal {
+    pub(crate) mu: Vec<f64>,
+    pub(crate) cov: Vec<f64>,
 }
 
-impl<'a> Distribution<'a> for FullNormal<'a> {
-    fn new(
-        params: Vec<&'a [f64]>
-    ) -> Self {
-        Self {
-            mu: params[0],
-            cov: params[1],
+impl FullNormal {
+    /// Create a new FullNormal, checking that `cov` is a square, symmetric and
+    /// positive semi-definite matrix of the same dimension as `mu`.
+    pub fn new(
+        mu: Vec<f64>,
+        cov: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let n = check_square_matrix("covariance", &cov, mu.len())?;
+        if let Some(i) = linalg::find_not_psd(&cov, n, COV_TOL) {
+            return Err(validation_error(
+                format!("FullNormal covariance is not positive semi-definite at diagonal entry ({},{})", i, i)
+            ));
+        }
+        return Ok(Self {
+            mu,
+            cov,
+        });
+    }
+
+    /// Create from standard deviations, i.e. a diagonal covariance with entries `stddevs[i]^2`.
+    pub fn from_stddevs(
+        mu: Vec<f64>,
+        stddevs: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        check_diagonal("standard deviation", &stddevs, mu.len())?;
+        let variances = stddevs.iter().map(|s| s * s).collect();
+        return Self::from_diag(mu, variances);
+    }
+
+    /// Create from variances, i.e. the diagonal of the covariance matrix.
+    pub fn from_diag(
+        mu: Vec<f64>,
+        variances: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        check_diagonal("variance", &variances, mu.len())?;
+        let n = variances.len();
+        let mut cov = vec![0.0; n*n];
+        for (i, v) in variances.iter().enumerate() {
+            cov[i*n + i] = *v;
         }
+        return Self::new(mu, cov);
+    }
+
+    /// Create from an information (inverse covariance) matrix, stored row-major, which must be
+    /// symmetric positive definite.
+    pub fn from_information_matrix(
+        mu: Vec<f64>,
+        info: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let n = check_square_matrix("information matrix", &info, mu.len())?;
+        let l = match linalg::cholesky(&info, n) {
+            Ok(l) => l,
+            Err(i) => return Err(validation_error(
+                format!("FullNormal information matrix is not positive definite at diagonal entry ({},{})", i, i)
+            )),
+        };
+        return Self::new(mu, linalg::cholesky_inverse(&l, n));
     }
 
-    /// Convert to JSON, notice change in type to `PackedFullNormal`
-    fn to_json(
+    pub fn mu(
         &self
-    ) -> String {
-        return PackedFullNormal {
-            mu: self.mu.to_vec(),
-            cov: self.cov.to_vec(),
+    ) -> &[f64] {
+        return &self.mu;
+    }
+
+    /// Covariance matrix, row-major.
+    pub fn cov(
+        &self
+    ) -> &[f64] {
+        return &self.cov;
+    }
+
+    /// Lower triangular Cholesky factor of the covariance, errors if the covariance is not
+    /// strictly positive definite, e.g. when a variance is zero.
+    pub fn cholesky(
+        &self
+    ) -> Result<Vec<f64>, Box<dyn Error>> {
+        let n = self.mu.len();
+        return linalg::cholesky(&self.cov, n).map_err(|i| validation_error(
+            format!("FullNormal covariance is not positive definite at diagonal entry ({},{})", i, i)
+        ));
+    }
+
+    /// Convert a Pose3 measurement `[x, y, z, ωx, ωy, ωz]` with the rotation as rotation vector
+    /// to `[x, y, z, roll, pitch, yaw]`, the covariance is propagated to first order.
+    pub fn pose3_rotvec_to_euler(
+        &self
+    ) -> Result<Self, Box<dyn Error>> {
+        return self.pose3_convert_rotation(|w| geometry::quat_to_euler(&geometry::so3_exp(w)));
+    }
+
+    /// Convert a Pose3 measurement `[x, y, z, roll, pitch, yaw]` to `[x, y, z, ωx, ωy, ωz]` with
+    /// the rotation as rotation vector, inverse of `pose3_rotvec_to_euler`.
+    pub fn pose3_euler_to_rotvec(
+        &self
+    ) -> Result<Self, Box<dyn Error>> {
+        return self.pose3_convert_rotation(|rpy| geometry::so3_log(&geometry::euler_to_quat(rpy)));
+    }
+
+    fn pose3_convert_rotation(
+        &self,
+        f: fn(&[f64; 3]) -> [f64; 3],
+    ) -> Result<Self, Box<dyn Error>> {
+        if self.mu.len() != 6 {
+            return Err(validation_error(
+                format!("Pose3 conversion requires dimension 6 but FullNormal has dimension {}", self.mu.len())
+            ));
+        }
+        let r = [self.mu[3], self.mu[4], self.mu[5]];
+        let fr = f(&r);
+        // central differences for the 3x3 rotation block of the Jacobian
+        let h = 1e-6;
+        let mut jac = vec![0.0; 36];
+        for i in 0..3 {
+            jac[i*6 + i] = 1.0;
+        }
+        for k in 0..3 {
+            let (mut rp, mut rm) = (r, r);
+            rp[k] += h;
+            rm[k] -= h;
+            let (fp, fm) = (f(&rp), f(&rm));
+            for i in 0..3 {
+                jac[(i + 3)*6 + k + 3] = geometry::wrap_angle(fp[i] - fm[i]) / (2.0 * h);
+            }
+        }
+        let mut mu = self.mu.clone();
+        mu[3..6].copy_from_slice(&fr);
+        return Self::new(mu, linalg::congruence(&jac, &self.cov, 6));
+    }
+}
+
+
+/// Check that `a` is a square, finite and symmetric matrix of dimension `dim`, returns the dimension.
+fn check_square_matrix(
+    name: &str,
+    a: &[f64],
+    dim: usize,
+) -> Result<usize, Box<dyn Error>> {
+    let n = match linalg::square_dim(a) {
+        Some(n) => n,
+        None => return Err(validation_error(
+            format!("FullNormal {} with {} entries is not square", name, a.len())
+        )),
+    };
+    if n != dim {
+        return Err(validation_error(
+            format!("FullNormal {} is {}x{} but mean has dimension {}", name, n, n, dim)
+        ));
+    }
+    if let Some((i, j)) = linalg::find_not_finite(a, n) {
+        return Err(validation_error(
+            format!("FullNormal {} has non-finite value {} at entry ({},{})", name, a[i*n + j], i, j)
+        ));
+    }
+    if let Some((i, j)) = linalg::find_asymmetry(a, n, COV_TOL) {
+        return Err(validation_error(
+            format!("FullNormal {} is not symmetric at entry ({},{})", name, i, j)
+        ));
+    }
+    return Ok(n);
+}
+
+/// Check that the diagonal entries are finite and non-negative.
+fn check_diagonal(
+    name: &str,
+    d: &[f64],
+    dim: usize,
+) -> Result<(), Box<dyn Error>> {
+    if d.len() != dim {
+        return Err(validation_error(
+            format!("FullNormal has {} {} entries but mean has dimension {}", d.len(), name, dim)
+        ));
+    }
+    if let Some(i) = d.iter().position(|v| !v.is_finite() || *v < 0.0) {
+        return Err(validation_error(
+            format!("FullNormal {} at index {} must be finite and non-negative, got {}", name, i, d[i])
+        ));
+    }
+    return Ok(());
+}
+
+impl Distribution for FullNormal {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::FullNormal(PackedFullNormal {
             type_: "IncrementalInference.PackedFullNormal".to_string(),
-        }.to_json();
+            mu: self.mu.clone(),
+            cov: self.cov.clone(),
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        return self.mu.len();
+    }
+}
+
+
+/// One dimensional normal distribution specified by mean and standard deviation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Normal {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl Normal {
+    pub fn new(
+        mu: f64,
+        sigma: f64,
+    ) -> Result<Self, Box<dyn Error>> {
+        if !(0.0 <= sigma && sigma.is_finite()) {
+            return Err(validation_error(
+                format!("Normal requires a finite non-negative sigma, got {}", sigma)
+            ));
+        }
+        return Ok(Self {
+            mu,
+            sigma,
+        });
+    }
+}
+
+impl Distribution for Normal {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::Normal(PackedNormal {
+            type_: "IncrementalInference.PackedNormal".to_string(),
+            mu: self.mu,
+            sigma: self.sigma,
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        return 1;
+    }
+}
+
+
+/// One dimensional uniform distribution on the interval [a, b].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Uniform {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Uniform {
+    pub fn new(
+        a: f64,
+        b: f64,
+    ) -> Result<Self, Box<dyn Error>> {
+        if !(a < b) {
+            return Err(validation_error(
+                format!("Uniform requires a < b, got a={} b={}", a, b)
+            ));
+        }
+        return Ok(Self {
+            a,
+            b,
+        });
+    }
+}
+
+impl Distribution for Uniform {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::Uniform(PackedUniform {
+            type_: "IncrementalInference.PackedUniform".to_string(),
+            a: self.a,
+            b: self.b,
+            PackedSamplableTypeJSON: "IncrementalInference.PackedUniform".to_string(),
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        return 1;
+    }
+}
+
+
+/// One dimensional Rayleigh distribution with scale sigma.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rayleigh {
+    pub sigma: f64,
+}
+
+impl Rayleigh {
+    pub fn new(
+        sigma: f64,
+    ) -> Result<Self, Box<dyn Error>> {
+        if !(0.0 < sigma && sigma.is_finite()) {
+            return Err(validation_error(
+                format!("Rayleigh requires a finite positive sigma, got {}", sigma)
+            ));
+        }
+        return Ok(Self {
+            sigma,
+        });
+    }
+}
+
+impl Distribution for Rayleigh {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::Rayleigh(PackedRayleigh {
+            type_: "IncrementalInference.PackedRayleigh".to_string(),
+            sigma: self.sigma,
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        return 1;
+    }
+}
+
+
+/// Multidimensional normal distribution with diagonal covariance, specified by means and standard deviations.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiagNormal {
+    pub mu: Vec<f64>,
+    pub sigma: Vec<f64>,
+}
+
+impl DiagNormal {
+    pub fn new(
+        mu: Vec<f64>,
+        sigma: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if mu.len() != sigma.len() {
+            return Err(validation_error(
+                format!("DiagNormal mean has dimension {} but sigma has {}", mu.len(), sigma.len())
+            ));
+        }
+        if let Some(i) = sigma.iter().position(|s| !(0.0 <= *s && s.is_finite())) {
+            return Err(validation_error(
+                format!("DiagNormal requires finite non-negative sigma, got sigma[{}]={}", i, sigma[i])
+            ));
+        }
+        return Ok(Self {
+            mu,
+            sigma,
+        });
+    }
+}
+
+impl Distribution for DiagNormal {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::DiagNormal(PackedDiagNormal {
+            type_: "IncrementalInference.PackedDiagNormal".to_string(),
+            mu: self.mu.clone(),
+            diag: self.sigma.clone(),
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        return self.mu.len();
+    }
+}
+
+
+/// Nonparametric belief on a manifold, represented by kernel points and a bandwidth.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManifoldKernelDensity {
+    pub varType: String,
+    pub pts: Vec<Vec<f64>>,
+    pub bw: Vec<f64>,
+    pub partial: Vec<i64>,
+    pub infoPerCoord: Vec<f64>,
+}
+
+impl ManifoldKernelDensity {
+    /// Create a new kernel density over variable type `varType`, each point must have the manifold
+    /// dimension of `varType` and the bandwidth is either empty (computed by the solver) or one entry per dimension.
+    #[allow(non_snake_case)]
+    pub fn new<V: VariableType>(
+        varType: V,
+        pts: Vec<Vec<f64>>,
+        bw: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let dim = varType.dim();
+        if let Some(i) = pts.iter().position(|p| p.len() != dim) {
+            return Err(validation_error(
+                format!("ManifoldKernelDensity point {} has dimension {} but {} requires {}", i, pts[i].len(), varType.type_str(), dim)
+            ));
+        }
+        if !bw.is_empty() && bw.len() != dim {
+            return Err(validation_error(
+                format!("ManifoldKernelDensity bandwidth has dimension {} but {} requires {}", bw.len(), varType.type_str(), dim)
+            ));
+        }
+        return Ok(Self {
+            varType: varType.type_str(),
+            pts,
+            bw,
+            partial: Vec::new(),
+            infoPerCoord: vec![1.0; dim],
+        });
+    }
+
+    /// Same as `new` but only the coordinates listed in `partial` are measured, e.g. `vec![0, 1]`
+    /// for the (x,y) translation of a Pose2.  Indices are zero-based and stored one-based as used in Julia.
+    #[allow(non_snake_case)]
+    pub fn new_partial<V: VariableType>(
+        varType: V,
+        pts: Vec<Vec<f64>>,
+        bw: Vec<f64>,
+        partial: Vec<usize>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let dim = varType.dim();
+        if partial.is_empty() {
+            return Err(validation_error(
+                "ManifoldKernelDensity partial must not be empty".to_owned()
+            ));
+        }
+        for (i, p) in partial.iter().enumerate() {
+            if dim <= *p || partial[..i].contains(p) {
+                return Err(validation_error(
+                    format!("ManifoldKernelDensity partial={:?} has invalid or repeated coordinate {} for dimension {}", partial, p, dim)
+                ));
+            }
+        }
+        if !bw.is_empty() && bw.len() != partial.len() {
+            return Err(validation_error(
+                format!("ManifoldKernelDensity bandwidth has dimension {} but partial has {}", bw.len(), partial.len())
+            ));
+        }
+        let mut mkd = Self::new(varType, pts, Vec::new())?;
+        mkd.bw = bw;
+        mkd.infoPerCoord = (0..dim).map(|i| if partial.contains(&i) { 1.0 } else { 0.0 }).collect();
+        mkd.partial = partial.iter().map(|p| (*p + 1) as i64).collect();
+        return Ok(mkd);
+    }
+}
+
+impl Distribution for ManifoldKernelDensity {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return PackedDistribution::ManifoldKernelDensity(PackedManifoldKernelDensity {
+            type_: "IncrementalInference.PackedManifoldKernelDensity".to_string(),
+            varType: self.varType.clone(),
+            pts: self.pts.clone(),
+            bw: self.bw.clone(),
+            partial: self.partial.clone(),
+            infoPerCoord: self.infoPerCoord.clone(),
+        });
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        if !self.partial.is_empty() {
+            return self.partial.len();
+        }
+        return self.pts.first().map(|p| p.len()).unwrap_or(self.bw.len());
     }
 }
 
 
+// ======================== Packed ==========================
+
+
+/// Owned and type-erased distribution in the IncrementalInference packed JSON layout.
+/// Serializes as the inner packed type, and deserializes by dispatching on the `_type` field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PackedDistribution {
+    FullNormal(PackedFullNormal),
+    Normal(PackedNormal),
+    Uniform(PackedUniform),
+    Rayleigh(PackedRayleigh),
+    DiagNormal(PackedDiagNormal),
+    ManifoldKernelDensity(PackedManifoldKernelDensity),
+}
+
+
 /// Multidimensional normal distribution specified by means and a covariance matrix.
 #[allow(non_snake_case)]
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PackedFullNormal {
+    #[serde(rename = "_type")]
+    pub type_: String,
     pub mu: Vec<f64>,
     pub cov: Vec<f64>,
-    pub type_: String,
 }
 
 
@@ -58,3 +553,160 @@ impl PackedFullNormal {
     }
 }
 
+
+impl PackedDistribution {
+    pub fn to_json(
+        &self
+    ) -> String {
+        serde_json::to_string(self).unwrap().to_string()
+    }
+
+    pub fn from_json(
+        json: &str
+    ) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl Distribution for PackedDistribution {
+    fn to_packed(
+        &self
+    ) -> PackedDistribution {
+        return self.clone();
+    }
+
+    fn dim(
+        &self
+    ) -> usize {
+        match self {
+            PackedDistribution::FullNormal(d) => d.mu.len(),
+            PackedDistribution::Normal(_) => 1,
+            PackedDistribution::Uniform(_) => 1,
+            PackedDistribution::Rayleigh(_) => 1,
+            PackedDistribution::DiagNormal(d) => d.mu.len(),
+            PackedDistribution::ManifoldKernelDensity(d) => {
+                if !d.partial.is_empty() {
+                    d.partial.len()
+                } else {
+                    d.pts.first().map(|p| p.len()).unwrap_or(d.bw.len())
+                }
+            },
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PackedDistribution {
+    fn deserialize<De: serde::Deserializer<'de>>(
+        deserializer: De
+    ) -> Result<Self, De::Error> {
+        use serde::de::Error as _;
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let _type = value.get("_type")
+            .and_then(|t| t.as_str())
+            .ok_or(De::Error::missing_field("_type"))?
+            .to_owned();
+        // match on the type name only, e.g. "IncrementalInference.PackedNormal" or "PackedNormal"
+        let res = match _type.rsplit('.').next().unwrap_or("") {
+            "PackedFullNormal" => serde_json::from_value(value).map(PackedDistribution::FullNormal),
+            "PackedNormal" => serde_json::from_value(value).map(PackedDistribution::Normal),
+            "PackedUniform" => serde_json::from_value(value).map(PackedDistribution::Uniform),
+            "PackedRayleigh" => serde_json::from_value(value).map(PackedDistribution::Rayleigh),
+            "PackedDiagNormal" => serde_json::from_value(value).map(PackedDistribution::DiagNormal),
+            "PackedManifoldKernelDensity" => serde_json::from_value(value).map(PackedDistribution::ManifoldKernelDensity),
+            _ => return Err(De::Error::custom(format!("unsupported distribution _type {}", _type))),
+        };
+        return res.map_err(De::Error::custom);
+    }
+}
+
+
+/// One dimensional normal distribution specified by mean and standard deviation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedNormal {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+
+/// One dimensional uniform distribution on the interval [a, b].
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedUniform {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub a: f64,
+    pub b: f64,
+    #[serde(default)]
+    pub PackedSamplableTypeJSON: String,
+}
+
+
+/// One dimensional Rayleigh distribution with scale sigma.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedRayleigh {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub sigma: f64,
+}
+
+
+/// Multidimensional normal distribution with diagonal covariance, `diag` holds the standard deviations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedDiagNormal {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub mu: Vec<f64>,
+    pub diag: Vec<f64>,
+}
+
+
+/// Nonparametric belief on a manifold, represented by kernel points and a bandwidth.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedManifoldKernelDensity {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub varType: String,
+    pub pts: Vec<Vec<f64>>,
+    #[serde(default)]
+    pub bw: Vec<f64>,
+    #[serde(default)]
+    pub partial: Vec<i64>,
+    #[serde(default)]
+    pub infoPerCoord: Vec<f64>,
+}
+
+
+/// Categorical distribution over the indices of `p`, e.g. the diversity of a Mixture factor.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackedCategorical {
+    #[serde(rename = "_type")]
+    pub type_: String,
+    pub p: Vec<f64>,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_information_matrix() {
+        let cov = vec![
+            0.04, 0.01, 0.0,
+            0.01, 0.09, 0.0,
+            0.0,  0.0,  0.0001,
+        ];
+        let info = linalg::cholesky_inverse(&linalg::cholesky(&cov, 3).unwrap(), 3);
+        let res = FullNormal::from_information_matrix(vec![1.0, 2.0, 0.5], info).unwrap();
+        for (x, y) in res.cov().iter().zip(&cov) {
+            assert!((x - y).abs() <= 1e-12, "{:?} != {:?}", res.cov(), cov);
+        }
+        assert_eq!(res.mu(), &[1.0, 2.0, 0.5]);
+
+        assert!(FullNormal::from_information_matrix(vec![0.0, 0.0], vec![1.0, 2.0, 2.0, 1.0]).is_err());
+        assert!(FullNormal::from_information_matrix(vec![0.0, 0.0], vec![1.0, 0.0, 0.0]).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use crate::{
+  BlobEntry,
+  Utc,
+  Uuid,
+};
+
+/// A factor graph of an org, as stored in the NavAbility API, see `services::post_get_factorgraph`.
+/// Also used as marker type in `NvaNode<Factorgraph>`.
+#[derive(Debug, Clone, Default)]
+#[allow(non_snake_case)]
+pub struct Factorgraph {
+    pub id: Option<Uuid>,
+    pub label: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub _version: String,
+    pub createdTimestamp: chrono::DateTime::<Utc>,
+    pub lastUpdatedTimestamp: Option<chrono::DateTime::<Utc>>,
+    pub numVariables: i64,
+    pub numFactors: i64,
+    /// base64 encoded JSON, decode with `HasMetadata::get_metadata`
+    pub metadata: Option<String>,
+    pub solveKeys: Option<Vec<String>>,
+    pub blobEntries: Option<HashMap<String, BlobEntry>>,
+    /// label and `lastUpdatedTimestamp` of each connected agent, i.e. not when it was connected
+    pub agents: Option<Vec<(String,chrono::DateTime<Utc>)>>,
+    /// label and `lastUpdatedTimestamp` of each connected model, same as `agents`
+    pub models: Option<Vec<(String,chrono::DateTime<Utc>)>>,
+}
@@ -1,6 +1,4 @@
 
-use std::ptr::null;
-
 use uuid::Uuid;
 use chrono::{
     DateTime,
@@ -11,17 +9,27 @@ use chrono::{
 use serde::{Deserialize, Serialize};
 
 
+/// Factor function node data, in the `PackedFunctionNodeData` layout of DistributedFactorGraphs.jl.
+/// `fnc` holds the packed factor type, e.g. `{"Z": {"_type": "IncrementalInference.PackedFullNormal", ...}}`.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct FunctionData {
-  pub eliminated:  Option<bool>,
-  pub potialused: Option<bool>,
-  pub edgeIDs: Option<Vec<i64>>,
-  pub fnc: String,
+  #[serde(default)]
+  pub eliminated: bool,
+  #[serde(default, alias = "potialused")]
+  pub potentialused: bool,
+  #[serde(default)]
+  pub edgeIDs: Vec<i64>,
+  pub fnc: serde_json::Value,
+  #[serde(default)]
   pub multihypo: Vec<f64>,
-  pub certainhypo: Option<Vec<i64>>,
+  #[serde(default)]
+  pub certainhypo: Vec<i64>,
+  #[serde(default)]
   pub nullhypo: f64,
-  pub solveInProgress: Option<i64>,
+  #[serde(default)]
+  pub solveInProgress: i64,
+  #[serde(default)]
   pub inflation: f64
 }
 
@@ -37,29 +45,98 @@ pub struct FactorDFG<F> {
   pub nstime: Option<String>,
   pub fnctype: F,
   pub solvable: Option<i64>,
-  pub data: Option<String>,
+  pub data: Option<FunctionData>,
   pub metadata: Option<String>,
-  pub _version: Option<String>
+  pub _version: Option<String>,
+  /// When the factor was last changed in its graph, assigned by the graph.
+  pub lastUpdatedTimestamp: Option<DateTime<Utc>>,
 }
 
 
 
-#[allow(non_snake_case)]
-pub trait FactorType<'a, D: crate::Distribution<'a>> {
-  fn new(Z: D) -> Self;
+/// Owned and type-erased factor, used when reading factors back from the API.
+/// Serializes as the packed pair `{"fnctype": ..., "fnc": ...}`, and factor types
+/// not supported by the SDK are either decoded as `Custom` if registered, or kept verbatim as `Unknown`.
+#[derive(Debug, Clone)]
+pub enum AnyFactor {
+  PriorPoint2(PriorPoint2<crate::PackedDistribution>),
+  PriorPoint3(PriorPoint3<crate::PackedDistribution>),
+  PriorPose2(PriorPose2<crate::PackedDistribution>),
+  PriorPose3(PriorPose3<crate::PackedDistribution>),
+  Point2Point2(Point2Point2<crate::PackedDistribution>),
+  Point3Point3(Point3Point3<crate::PackedDistribution>),
+  Pose2Pose2(Pose2Pose2<crate::PackedDistribution>),
+  Pose3Pose3(Pose3Pose3<crate::PackedDistribution>),
+  Pose2Point2Range(Pose2Point2Range<crate::PackedDistribution>),
+  Pose2Point2Bearing(Pose2Point2Bearing<crate::PackedDistribution>),
+  Pose2Point2BearingRange(Pose2Point2BearingRange<crate::PackedDistribution, crate::PackedDistribution>),
+  Point2Point2Range(Point2Point2Range<crate::PackedDistribution>),
+  Pose3Point3(Pose3Point3<crate::PackedDistribution>),
+  Mixture(PackedMixture),
+  Custom(std::sync::Arc<dyn CustomFactor>),
+  Unknown {
+    fnctype: String,
+    raw_json: String,
+  },
+}
+
+
+/// Filter options for listing factors, all fields are optional and combined with AND.
+#[derive(Debug, Clone, Default)]
+pub struct ListFactorsFilter {
+  /// factor must have this tag
+  pub tag: Option<String>,
+  /// factor solvable must be greater than or equal to this value
+  pub solvable: Option<i64>,
+  /// factor must be connected to the variable with this label
+  pub variable: Option<String>,
+  /// factor fnctype must match, e.g. "Pose2Pose2"
+  pub fnctype: Option<String>,
+}
+
+
+/// Factor types that can be added to a factor graph, implement this trait (or use `GenCustomFactor!`)
+/// for user-defined factors from other Julia packages.
+pub trait FactorType {
+  /// Fully qualified type including the module namespace, e.g. "RoME.Pose2Pose2".
   fn type_str(&self) -> String;
+  /// Packed payload of the factor, i.e. the `fnc` part of the factor data, e.g. `{"Z": {...}}`.
+  fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn std::error::Error>>;
+  /// Number of variables the factor must connect, `None` if it is not checked.
+  fn arity(&self) -> Option<usize> {
+    return None;
+  }
 }
 
 
+/// User-defined factor types that can be decoded at runtime, see `register_factor_type`.
+pub trait CustomFactor: FactorType + std::fmt::Debug + Send + Sync {
+  /// Allows downcasting to the concrete type, e.g. `f.as_any().downcast_ref::<MyFactor>()`.
+  fn as_any(&self) -> &dyn std::any::Any;
+}
 
+impl<T: FactorType + std::fmt::Debug + Send + Sync + 'static> CustomFactor for T {
+  fn as_any(&self) -> &dyn std::any::Any {
+    return self;
+  }
+}
 
-#[derive(Debug, Clone)]
+
+/// Factor types defined by a single measurement distribution `Z`, e.g. usable as `Mixture` components.
+pub trait DistrFactor: FactorType {
+  fn packed_distribution(&self) -> crate::PackedDistribution;
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PriorPoint2<T> {
     pub Z: T
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PriorPoint3<T> {
     pub Z: T
@@ -70,7 +147,7 @@ pub struct PriorPoint3<T> {
 /// between the variables, e.g. `FullNormal([1;0;0], diagm(0.01*ones(3)))`.
 ///
 /// Example value: Z = `FullNormal(zeros(3), diagm(0.01*ones(3)))`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PriorPose2<T> {
     pub Z: T
@@ -81,21 +158,21 @@ pub struct PriorPose2<T> {
 /// between the variables, e.g. `FullNormal([1;zeros(5)], diagm(0.01*ones(6)))`.
 ///
 /// Example value: Z = `FullNormal(zeros(6), diagm(0.01*ones(6)))`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PriorPose3<T> {
     pub Z: T
 }
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct Point2Point2<T> {
     pub Z: T
 }
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct Point3Point3<T> {
     pub Z: T
@@ -106,19 +183,97 @@ pub struct Point3Point3<T> {
 /// between the variables, e.g. `FullNormal([1;0;0], diagm(0.01*ones(3)))`.
 ///
 /// Example value: Z = `FullNormal(zeros(3), diagm(0.01*ones(3)))`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct Pose2Pose2<T> {
     pub Z: T
 }
 
 
+/// Create a Pose2->Pose2 factor from a measurement of only some of the (x,y,th) coordinates,
+/// e.g. the (x,y) translation only with `partial = vec![0, 1]` and a 2x2 `FullNormal`.
+/// Create with `PartialPose2Pose2::new`, which checks `Z` has one dimension per partial coordinate.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_snake_case)]
+pub struct PartialPose2Pose2<T> {
+    pub(crate) Z: T,
+    pub(crate) partial: Vec<usize>,
+}
+
+
 /// Create a Pose3->Pose3 factor with a distribution Z representing the (x,y,z,a,b,c) relationship
 /// between the variables, e.g. `FullNormal([1;zeros(5)], diagm(0.01*ones(6)))`.
 ///
 /// Example value: Z = `FullNormal(zeros(6), diagm(0.01*ones(6)))`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct Pose3Pose3<T> {
     pub Z: T
 }
+
+
+/// Create a Pose2->Point2 range factor with a one dimensional distribution Z, e.g. `Normal(10.0, 0.1)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Pose2Point2Range<T> {
+    pub Z: T
+}
+
+
+/// Create a Pose2->Point2 bearing factor with a one dimensional distribution Z in radians,
+/// measured relative to the pose heading, e.g. `Normal(0.5, 0.05)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Pose2Point2Bearing<T> {
+    pub Z: T
+}
+
+
+/// Create a Pose2->Point2 bearing and range factor with separate one dimensional distributions,
+/// e.g. bearing `Normal(0.5, 0.05)` and range `Normal(10.0, 0.1)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pose2Point2BearingRange<B, R> {
+    #[serde(rename = "bearstr")]
+    pub bearing: B,
+    #[serde(rename = "rangstr")]
+    pub range: R,
+}
+
+
+/// Create a Point2->Point2 range factor with a one dimensional distribution Z, e.g. `Normal(10.0, 0.1)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Point2Point2Range<T> {
+    pub Z: T
+}
+
+
+/// Create a Pose3->Point3 factor with a distribution Z representing the (x,y,z) position
+/// of the point in the pose frame, e.g. `FullNormal([1;0;0], diagm(0.01*ones(3)))`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Pose3Point3<T> {
+    pub Z: T
+}
+
+
+/// Create a Mixture factor where the measurement of factor type F is one of several components,
+/// selected with probability `weights`, e.g. a Pose2Point2Range with either a `Normal` or `Uniform` range.
+/// Create with `Mixture::new`, which checks there is at least one component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mixture<F> {
+    pub(crate) components: Vec<F>,
+    pub(crate) weights: Vec<f64>,
+}
+
+
+/// Packed Mixture factor in the IncrementalInference `PackedMixture` layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct PackedMixture {
+    pub N: i64,
+    pub F_: String,
+    pub S: Vec<String>,
+    pub components: Vec<crate::PackedDistribution>,
+    pub diversity: crate::PackedCategorical,
+}
@@ -0,0 +1,53 @@
+
+/// Options for `export_graph`, writing a factor graph to a DistributedFactorGraphs `.tar.gz` archive.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// number of variables or factors read per request, 0 uses the default of 100
+    pub page_size: usize,
+    /// also write the blob entries of each variable, the blobs themselves are never exported
+    pub include_blob_entries: bool,
+}
+
+/// Options for `import_graph`, reading a DistributedFactorGraphs `.tar.gz` archive into a factor graph.
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// number of variables or factors written per batch, 0 uses the default of 100
+    pub batch_size: usize,
+    /// skip variables, PPEs, blob entries and factors that already exist in the target graph,
+    /// e.g. to continue an import that failed part way
+    pub resume: bool,
+    /// only read and validate the archive, nothing is written to the target graph
+    pub dry_run: bool,
+    /// also import the blob entries of each variable, the blobs themselves are not in the archive
+    pub include_blob_entries: bool,
+}
+
+/// Outcome of `import_graph`, for a dry run what would be imported.
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// labels of the variables added
+    pub variables: Vec<String>,
+    /// labels of the factors added
+    pub factors: Vec<String>,
+    /// number of PPEs added
+    pub ppes: usize,
+    /// number of blob entries added
+    pub blob_entries: usize,
+    /// labels of variables and factors skipped because they already exist, see `ImportOptions::resume`
+    pub skipped: Vec<String>,
+    /// problems found while validating the archive, an import only starts if there are none
+    pub problems: Vec<String>,
+    /// for a dry run, factors of types not supported by the SDK, these are imported unchanged
+    pub warnings: Vec<String>,
+    /// number of solver data entries not imported, the graph needs to be solved again
+    pub solver_data_skipped: usize,
+}
+
+/// Plain text pose graph formats, see `read_pose_graph` and `write_pose_graph`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoseGraphFormat {
+    /// g2o with `VERTEX_SE2`, `EDGE_SE2`, `VERTEX_SE3:QUAT` and `EDGE_SE3:QUAT` lines
+    G2o,
+    /// TORO with `VERTEX2`, `EDGE2`, `VERTEX3` and `EDGE3` lines, 3D rotations as roll, pitch, yaw
+    Toro,
+}
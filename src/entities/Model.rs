@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use crate::{
+  BlobEntry,
+  Utc,
+  Uuid,
+};
+
+/// A robot model of an org, e.g. a hardware platform shared by several agents, see
+/// `services::post_get_model`.  Also used as marker type in `NvaNode<Model>`.
+#[derive(Debug, Clone, Default)]
+#[allow(non_snake_case)]
+pub struct Model {
+    pub id: Option<Uuid>,
+    pub label: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub _version: String,
+    pub createdTimestamp: chrono::DateTime::<Utc>,
+    pub lastUpdatedTimestamp: Option<chrono::DateTime::<Utc>>,
+    /// base64 encoded JSON, decode with `HasMetadata::get_metadata`
+    pub metadata: Option<String>,
+    pub blobEntries: Option<HashMap<String, BlobEntry>>,
+    pub agents: Option<Vec<(String,chrono::DateTime<Utc>)>>,
+    pub fgs: Option<Vec<(String,chrono::DateTime<Utc>)>>,
+}
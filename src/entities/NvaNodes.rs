@@ -10,12 +10,6 @@ pub struct Org {
     description: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct Model {}
-
-#[derive(Debug, Clone)]
-pub struct Factorgraph {}
-
 #[derive(Clone,Debug)]
 pub struct NvaNode<T> {
     pub namespace: Uuid,
@@ -0,0 +1,67 @@
+use crate::{
+    Utc,
+    Uuid,
+};
+
+
+/// Kind of graph node found by `diff`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffNodeType {
+    Variable,
+    Factor,
+    PPE,
+    BlobEntry,
+}
+
+/// A node that differs between two graphs, see `diff`.
+#[derive(Debug, Clone)]
+pub struct DiffNode {
+    /// deterministic id of the node, the same in both graphs
+    pub id: Uuid,
+    pub node_type: DiffNodeType,
+    /// label of the variable or factor, for PPEs and blob entries the label of their variable
+    pub label: String,
+    /// solve key of a PPE or label of a blob entry
+    pub key: Option<String>,
+    pub local_updated: Option<chrono::DateTime<Utc>>,
+    pub remote_updated: Option<chrono::DateTime<Utc>>,
+}
+
+/// Differences between a local and a remote graph, see `diff`.
+#[derive(Debug, Clone, Default)]
+pub struct GraphDiff {
+    /// nodes only in the local graph
+    pub added: Vec<DiffNode>,
+    /// nodes only in the remote graph
+    pub removed: Vec<DiffNode>,
+    /// nodes in both graphs with different content
+    pub changed: Vec<DiffNode>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        return self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty();
+    }
+}
+
+/// Which side of `sync` is kept when a node changed in both graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConflictPolicy {
+    /// push the local node
+    LocalWins,
+    /// pull the remote node
+    RemoteWins,
+    /// keep the node with the later `lastUpdatedTimestamp`, the local node on a tie.  A node without
+    /// `lastUpdatedTimestamp` counts as the newer one, the local node if both have none.
+    #[default]
+    Newest,
+}
+
+/// Outcome of `sync`.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// nodes written to the remote graph
+    pub pushed: Vec<DiffNode>,
+    /// nodes written to the local graph
+    pub pulled: Vec<DiffNode>,
+}
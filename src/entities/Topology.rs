@@ -0,0 +1,14 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+
+/// Which variables and factors are connected in a factor graph, read with `getAdjacency` for
+/// topology queries such as shortest paths when the backend cannot answer them directly.  It is a
+/// snapshot and is not updated when the graph changes, reuse it for several queries on an
+/// unchanged graph.  Variables and factors are both nodes, and each factor is adjacent to its
+/// `variableOrderSymbols_`.
+#[derive(Debug, Clone, Default)]
+pub struct Adjacency {
+    pub variables: BTreeSet<String>,
+    pub factors: BTreeSet<String>,
+    pub adjacency: BTreeMap<String, BTreeSet<String>>,
+}
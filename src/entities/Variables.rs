@@ -5,10 +5,11 @@ use crate::{
     BlobEntry,
     // SDK_VERSION,
 };
+use serde::{Deserialize, Serialize};
 
 
 /// Data container to store Parameteric Point Estimate (PPE) for mean and max.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct MeanMaxPPE {
     pub id: Option<Uuid>,
@@ -23,7 +24,7 @@ pub struct MeanMaxPPE {
 }
 
 /// Packed VariableNodeData structure for serializing DFGVariables.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct PackedVariableNodeData {
     pub id: Option<Uuid>,
@@ -51,13 +52,14 @@ pub struct PackedVariableNodeData {
 
 
 /// The Variable information packed in a way that accomdates multi-lang using json.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct VariableDFG {
     pub id: Option<Uuid>,
     pub label: String,
     pub tags: Vec<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "nstime_string")]
     pub nstime: usize,
     pub ppes: Vec<MeanMaxPPE>,
     pub blobEntries: Vec<BlobEntry>,
@@ -66,4 +68,68 @@ pub struct VariableDFG {
     pub metadata: String,
     pub solvable: i32,
     pub solverData: Vec<PackedVariableNodeData>,
+    /// When the variable was last changed in its graph, assigned by the graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastUpdatedTimestamp: Option<chrono::DateTime<Utc>>,
+}
+
+
+/// DFG packs `nstime` as a string, also accept plain numbers when reading.
+mod nstime_string {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        nstime: &usize,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&nstime.to_string());
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<usize, D::Error> {
+        return match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) => s.parse::<usize>().map_err(D::Error::custom),
+            serde_json::Value::Number(n) => n.as_u64().map(|n| n as usize).ok_or(D::Error::custom("nstime must be a positive integer")),
+            v => Err(D::Error::custom(format!("unexpected nstime {}", v))),
+        };
+    }
+}
+
+
+/// Variable types (i.e. manifolds) that a VariableDFG can represent, see Caesar.jl.
+pub trait VariableType {
+    /// Fully qualified Julia type, e.g. "RoME.Pose2"
+    fn type_str(&self) -> String;
+    /// Manifold dimension of the variable, e.g. 3 for Pose2
+    fn dim(&self) -> usize;
 }
+
+
+/// One dimensional Euclidean position, same as `ContinuousScalar`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Position1;
+
+/// Position in the plane (x,y).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Point2;
+
+/// Position in space (x,y,z).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Point3;
+
+/// Position and heading in the plane (x,y,th).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pose2;
+
+/// Position and orientation in space (x,y,z,a,b,c).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pose3;
+
+/// Alias of `IncrementalInference.Position{1}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContinuousScalar;
+
+/// N dimensional Euclidean position, alias of `IncrementalInference.Position{N}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContinuousEuclid<const N: usize>;
@@ -0,0 +1,8 @@
+/// Options for `to_dot` and `to_cytoscape_json`.
+#[derive(Debug, Clone, Default)]
+pub struct PlotOptions {
+    /// group nodes by their tag starting with this prefix, e.g. `"SESSION_"` draws one cluster per
+    /// session tag, nodes without such a tag are not clustered.  Nodes do not reference the agent
+    /// that added them, to cluster by agent tag the nodes when adding them, e.g. `"AGENT_"`.
+    pub cluster_by: Option<String>,
+}
@@ -7,6 +7,10 @@ use std::{
 };
 use serde::Serialize;
 use uuid::Uuid;
+
+// used by exported macros, e.g. GenCustomFactor!
+#[doc(hidden)]
+pub use serde_json;
 use chrono::{
     self, 
     Utc
@@ -47,6 +51,8 @@ pub use crate::common_traits::*;
 // type and file name are the same and requires precision import
 pub use crate::Agent::Agent;
 pub use crate::BlobEntry::BlobEntry;
+pub use crate::Factorgraph::Factorgraph;
+pub use crate::Model::Model;
 
 pub mod utils;
 pub use crate::utils::*;
@@ -268,6 +274,116 @@ pub struct ListVariables;
 pub struct AddVariable;
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/AddVariables.gql",
+    response_derives = "Debug"
+)]
+pub struct AddVariables;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/AddSolverData.gql",
+    response_derives = "Debug"
+)]
+pub struct AddSolverData;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdateSolverData.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdateSolverData;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteSolverData.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteSolverData;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdateVariable.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdateVariable;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteVariable.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteVariable;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/AddPPE.gql",
+    response_derives = "Debug"
+)]
+pub struct AddPPE;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdatePPE.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdatePPE;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeletePPE.gql",
+    response_derives = "Debug"
+)]
+pub struct DeletePPE;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetPPE.gql",
+    response_derives = "Debug"
+)]
+pub struct GetPPE;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/ListPPEKeys.gql",
+    response_derives = "Debug"
+)]
+pub struct ListPPEKeys;
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -278,6 +394,66 @@ pub struct AddVariable;
 pub struct AddFactors;
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetFactor.gql",
+    response_derives = "Debug"
+)]
+pub struct GetFactor;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/ListFactors.gql",
+    response_derives = "Debug"
+)]
+pub struct ListFactors;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteFactor.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteFactor;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdateFactor.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdateFactor;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetNeighbors.gql",
+    response_derives = "Debug"
+)]
+pub struct GetNeighbors;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetAdjacency.gql",
+    response_derives = "Debug"
+)]
+pub struct GetAdjacency;
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -288,6 +464,16 @@ pub struct AddFactors;
 pub struct AddBlobEntryAgent;
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/AddBlobEntryVariable.gql",
+    response_derives = "Debug"
+)]
+pub struct AddBlobEntryVariable;
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -308,6 +494,66 @@ pub struct AddFactorgraphBlobEntry;
 pub struct ConnectGraphAgent;
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DisconnectGraphFromAgent.gql",
+    response_derives = "Debug"
+)]
+pub struct DisconnectGraphAgent;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetFactorgraph.gql",
+    response_derives = "Debug"
+)]
+pub struct GetFactorgraph;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdateFactorgraphMetadata.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdateFactorgraphMetadata;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/CountFactorgraphNodes.gql",
+    response_derives = "Debug"
+)]
+pub struct CountFactorgraphNodes;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteFactorgraphNodes.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteFactorgraphNodes;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteFactorgraph.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteFactorgraph;
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -384,6 +630,66 @@ pub struct AddAgent;
 )]
 pub struct AddModel;
 
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/GetModel.gql",
+    response_derives = "Debug"
+)]
+pub struct GetModel;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/ListModelBlobEntries.gql",
+    response_derives = "Debug"
+)]
+pub struct ListModelBlobEntries;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/UpdateModel.gql",
+    response_derives = "Debug"
+)]
+pub struct UpdateModel;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/DeleteModel.gql",
+    response_derives = "Debug"
+)]
+pub struct DeleteModel;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/ConnectModelToAgent.gql",
+    response_derives = "Debug"
+)]
+pub struct ConnectModelAgent;
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/schema.json",
+    query_path = "src/gql/ConnectModelToGraph.gql",
+    response_derives = "Debug"
+)]
+pub struct ConnectModelGraph;
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -487,11 +793,11 @@ impl<T> GetId for NvaNode<T> {
 
 
 
-#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
     #[test]
     fn test_get_robots() {
         // parse datetime example 1
@@ -524,4 +830,88 @@ mod tests {
         let robotlist = get_robots_blocking(&client);
         // println!("robot list: {:?}", robotlist);
     }
+
+    #[test]
+    fn test_factordata_roundtrip() {
+        // Pose2Pose2 factor as written by DFG.packFactor, see tests/fixtures/README.md
+        let fixture = include_str!("../tests/fixtures/packedfactor_pose2pose2.json");
+        let packed: serde_json::Value = serde_json::from_str(fixture).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(packed["data"].as_str().unwrap()).unwrap();
+        assert_eq!(expected["fnc"]["Z"]["_type"], "IncrementalInference.PackedFullNormal");
+
+        let fromdfg = FactorDFG::from_dfg_json(fixture).unwrap();
+        assert!(matches!(fromdfg.fnctype, AnyFactor::Pose2Pose2(_)));
+        assert_eq!(fromdfg._version.as_deref(), packed["_version"].as_str());
+        let fd = fromdfg.data.as_ref().unwrap();
+        assert_eq!(fd.certainhypo, vec![1, 2]);
+        assert_eq!(fd.inflation, 3.0);
+        let _res: serde_json::Value = serde_json::from_str(&fd.to_json()).unwrap();
+        assert_eq!(_res, expected);
+
+        // the same factor created by the SDK packs to the same data
+        let z = FullNormal::new(
+            vec![10.0, 0.0, std::f64::consts::FRAC_PI_2],
+            vec![0.01, 0.0, 0.0, 0.0, 0.01, 0.0, 0.0, 0.0, 0.0001],
+        ).unwrap();
+        let mut f = FactorDFG::new(
+            vec!["x0".to_string(), "x1".to_string()],
+            Pose2Pose2::new(z),
+            Vec::new(),
+            None,
+            None,
+        ).unwrap();
+        f.label = fromdfg.label.clone();
+        let _res: serde_json::Value = serde_json::from_str(&f.data.as_ref().unwrap().to_json()).unwrap();
+        assert_eq!(_res, expected);
+        assert_eq!(f.to_any().unwrap().fnctype, fromdfg.fnctype);
+        assert!(f.is_same_factor(&fromdfg));
+
+        // and reads back from its own file layout
+        let _res = FactorDFG::from_dfg_json(&fromdfg.to_dfg_json().unwrap()).unwrap();
+        assert_eq!(_res._version, fromdfg._version);
+        assert!(_res.is_same_factor(&fromdfg));
+    }
+
+    #[test]
+    fn test_multihypo_validation() {
+        let z = FullNormal::new(
+            vec![10.0, 0.0, 0.0],
+            vec![0.01, 0.0, 0.0, 0.0, 0.01, 0.0, 0.0, 0.0, 0.0001],
+        ).unwrap();
+        let varlbls = vec!["x0".to_string(), "x1".to_string(), "x2".to_string()];
+        let _res = FactorDFG::new_multihypo(varlbls.clone(), Pose2Pose2::new(z.clone()), Vec::new(), None, None, vec![1.0, 0.5, 0.5], None);
+        assert!(_res.is_ok());
+        // fractions of the ambiguous variables must sum to 1
+        let _res = FactorDFG::new_multihypo(varlbls.clone(), Pose2Pose2::new(z.clone()), Vec::new(), None, None, vec![1.0, 0.5, 0.4], None);
+        assert!(_res.is_err());
+        let _res = FactorDFG::new_multihypo(varlbls.clone(), Pose2Pose2::new(z.clone()), Vec::new(), None, None, vec![1.0, 0.0, 1.0], None);
+        assert!(_res.is_err());
+        // packed by IncrementalInference with 0.0 for the certain variable
+        let _res = FactorDFG::new_multihypo(varlbls.clone(), Pose2Pose2::new(z.clone()), Vec::new(), None, None, vec![0.0, 0.5, 0.5], None);
+        assert!(_res.is_ok());
+
+        assert!(Mixture::<Pose2Pose2<FullNormal>>::new(Vec::new(), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_metadata_base64() {
+        let empty = utils::NodeMetadata::from_base64("e30=").unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_base64(), "e30=");
+
+        let mut md = utils::NodeMetadata::new();
+        md.set("robot", "R1").unwrap();
+        md.set("calib", serde_json::json!({"fx": 500.0, "fy": 500.0})).unwrap();
+        let mut other = utils::NodeMetadata::new();
+        other.set("calib", serde_json::json!({"fy": 510.0})).unwrap();
+        md.merge(&other);
+
+        let mut v = VariableDFG::new("x0", Pose2, None, None);
+        v.set_metadata(&md);
+        let _res = v.get_metadata().unwrap();
+        assert_eq!(_res, md);
+        assert_eq!(_res.get::<String>("robot").unwrap(), Some("R1".to_owned()));
+        assert_eq!(_res.get::<f64>("missing").unwrap(), None);
+        assert_eq!(_res.values["calib"], serde_json::json!({"fx": 500.0, "fy": 510.0}));
+    }
 }
@@ -5,6 +5,9 @@ pub mod NavAbilityDFG;
 #[allow(non_snake_case)]
 pub use NavAbilityDFG::*;
 
+#[allow(non_snake_case)]
+pub mod LocalDFG;
+
 #[allow(non_snake_case)]
 pub mod NavAbilityClient;
 #[allow(non_snake_case)]
@@ -50,6 +53,36 @@ pub mod Factors;
 #[allow(non_snake_case)]
 pub use Factors::*;
 
+#[allow(non_snake_case)]
+pub mod Topology;
+#[allow(non_snake_case)]
+pub use Topology::*;
+
+#[allow(non_snake_case)]
+pub mod FileDFG;
+#[allow(non_snake_case)]
+pub use FileDFG::*;
+
+#[allow(non_snake_case)]
+pub mod PoseGraph;
+#[allow(non_snake_case)]
+pub use PoseGraph::*;
+
+#[allow(non_snake_case)]
+pub mod Visualize;
+#[allow(non_snake_case)]
+pub use Visualize::*;
+
+#[allow(non_snake_case)]
+pub mod SyncDFG;
+#[allow(non_snake_case)]
+pub use SyncDFG::*;
+
+#[allow(non_snake_case)]
+pub mod FactorRegistry;
+#[allow(non_snake_case)]
+pub use FactorRegistry::*;
+
 #[allow(non_snake_case)]
 pub mod LegacyURS;
 #[allow(non_snake_case)]
@@ -470,10 +470,7 @@ pub async fn post_add_agent_entry(
   if let Some(sz) = entry.size {
     size_s = Some(format!("{}",sz));
   }
-  let mut metadata = entry.metadata.to_string();
-  if metadata.is_empty() {
-    metadata = "e30=".to_string();
-  }
+  let metadata = crate::utils::NodeMetadata::base64_or_empty(&entry.metadata);
   
   let variables = crate::add_blob_entry_agent::Variables {
     agent_label: agent_label.to_string(),
@@ -49,6 +49,10 @@ use get_variable::blobEntry_fields as GV_BlobEntryFields;
 BlobEntry_importers!(GB_BlobEntryFields);
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 BlobEntry_importers!(GV_BlobEntryFields);
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::list_model_blob_entries::blobEntry_fields as GM_BlobEntryFields;
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+BlobEntry_importers!(GM_BlobEntryFields);
 
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
@@ -61,6 +65,16 @@ use crate::get_agent::blobEntry_fields_summary as GA_BlobEntrySummary;
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 BlobEntry_importers_summary!(GA_BlobEntrySummary);
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::get_factorgraph::blobEntry_fields_summary as GF_BlobEntrySummary;
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+BlobEntry_importers_summary!(GF_BlobEntrySummary);
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::get_model::blobEntry_fields_summary as GM_BlobEntrySummary;
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+BlobEntry_importers_summary!(GM_BlobEntrySummary);
+
 
 impl BlobEntry {
   pub fn new() -> Self {
@@ -68,7 +82,7 @@ impl BlobEntry {
     be.blobId = Uuid::new_v4();
     be.blobstore = "default".to_string();
     be.origin = "NvaSDK.rs".to_string();
-    be.metadata = "e30=".to_string();
+    be.metadata = crate::utils::NodeMetadata::new().to_base64();
     be.createdTimestamp = Some(Utc::now());
     be.lastUpdatedTimestamp = be.createdTimestamp.clone();
     be._type = "BlobEntry".to_string(); // for self assemply typed usage elsewhere
@@ -0,0 +1,112 @@
+
+use std::{
+  collections::HashMap,
+  sync::{
+    Arc,
+    OnceLock,
+    RwLock,
+  },
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+  CustomFactor,
+  FactorType,
+};
+
+
+/// Deserializer from the packed factor JSON, i.e. the `fnc` part, to a user-defined factor type.
+pub type FactorDeserializer = fn(&str) -> Result<Arc<dyn CustomFactor>, serde_json::Error>;
+
+
+/// Implement `FactorType` for a user-defined factor that serializes its packed payload with serde.
+/// # Arguments
+/// * `$T` - the factor struct, must implement `Serialize`, and `Deserialize` for `register_factor_type`
+/// * `$namespace` - Julia module in which the factor is defined, e.g. "MyFactors"
+/// * `$arity` - number of variables the factor connects
+/// # Example
+/// ```ignore
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct WheelOdometry { Z: PackedDistribution, wheelbase: f64 }
+/// GenCustomFactor!(WheelOdometry, "MyFactors", 2);
+/// register_factor_type::<WheelOdometry>("MyFactors.WheelOdometry");
+/// ```
+/// # Note
+/// * Implement `FactorType` by hand instead if the packed payload needs custom serialization.
+#[macro_export]
+macro_rules! GenCustomFactor { 
+  ($T:ident, $namespace:literal, $arity:literal) => {
+    impl $crate::FactorType for $T {
+      fn type_str(&self) -> String {
+        return format!("{}.{}", $namespace, stringify!($T));
+      }
+
+      fn packed_fnc(&self) -> Result<$crate::serde_json::Value, Box<dyn ::std::error::Error>> {
+        return Ok($crate::serde_json::to_value(self)?);
+      }
+
+      fn arity(&self) -> Option<usize> {
+        return Some($arity);
+      }
+    }
+  }
+}
+
+
+fn factor_registry() -> &'static RwLock<HashMap<String, FactorDeserializer>> {
+  static REGISTRY: OnceLock<RwLock<HashMap<String, FactorDeserializer>>> = OnceLock::new();
+  return REGISTRY.get_or_init(|| RwLock::new(HashMap::new()));
+}
+
+fn deserialize_custom_factor<T: CustomFactor + DeserializeOwned + 'static>(
+  fnc: &str
+) -> Result<Arc<dyn CustomFactor>, serde_json::Error> {
+  let factor: T = serde_json::from_str(fnc)?;
+  return Ok(Arc::new(factor));
+}
+
+
+/// Register a user-defined factor type so that factors with this `fnctype` are decoded
+/// as `AnyFactor::Custom` when reading factors back from the API.
+/// # Arguments
+/// * `fnctype` - fully qualified type, e.g. "MyFactors.WheelOdometry", same as `FactorType::type_str`
+pub fn register_factor_type<T: FactorType + CustomFactor + DeserializeOwned + 'static>(
+  fnctype: &str
+) {
+  register_factor_deserializer(fnctype, deserialize_custom_factor::<T>);
+}
+
+/// Register a custom deserializer for `fnctype`, see `register_factor_type` for the common case.
+pub fn register_factor_deserializer(
+  fnctype: &str,
+  deserializer: FactorDeserializer,
+) {
+  factor_registry()
+    .write()
+    .expect("factor registry lock poisoned")
+    .insert(fnctype.to_owned(), deserializer);
+}
+
+/// Remove a registered factor type, returns `true` if it was registered.
+pub fn unregister_factor_type(
+  fnctype: &str
+) -> bool {
+  return factor_registry()
+    .write()
+    .expect("factor registry lock poisoned")
+    .remove(fnctype)
+    .is_some();
+}
+
+/// Decode a registered factor type, returns `None` if `fnctype` is not registered or the JSON does not match.
+pub fn decode_custom_factor(
+  fnctype: &str,
+  fnc: &str,
+) -> Option<Arc<dyn CustomFactor>> {
+  let deserializer = *factor_registry()
+    .read()
+    .expect("factor registry lock poisoned")
+    .get(fnctype)?;
+  return deserializer(fnc).ok();
+}
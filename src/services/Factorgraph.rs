@@ -17,6 +17,33 @@ use crate::{
   AddFactorgraphBlobEntry,
   add_factorgraph_blob_entry,
   FindOrgModelGraphs,
+  DisconnectGraphAgent,
+  disconnect_graph_agent,
+  CountFactorgraphNodes,
+  count_factorgraph_nodes,
+  DeleteFactorgraphNodes,
+  delete_factorgraph_nodes,
+  DeleteFactorgraph,
+  delete_factorgraph,
+  NavAbilityDFG,
+  NvaNode,
+  LocalDFG,
+  FactorGraphStore,
+  ImportOptions,
+  ImportReport,
+  ImportError,
+  ConflictError,
+  NotFoundError,
+  ValidationError,
+  Factorgraph,
+  BlobEntry,
+  parse_str_utc,
+  GetFactorgraph,
+  get_factorgraph,
+  UpdateFactorgraphMetadata,
+  update_factorgraph_metadata,
+  HasMetadata,
+  utils::NodeMetadata,
   to_console_debug,
   to_console_error,
   SDK_VERSION
@@ -24,6 +51,64 @@ use crate::{
 
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use std::collections::HashMap;
+
+
+// ===================== HELPERS ========================
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+impl Factorgraph {
+  /// Errors if the id or a timestamp of the graph or of a connected agent or model can not be
+  /// parsed, instead of leaving it out or using the epoch.
+  pub fn from_gql(
+    fggql: &get_factorgraph::GetFactorgraphFactorgraphs,
+  ) -> Result<Self, Box<dyn Error>> {
+    let summary = &fggql.factorgraph_fields_summary;
+    let full = &fggql.factorgraph_fields_full;
+
+    let mut fg = Factorgraph::default();
+    fg.label = summary.label.clone();
+    fg.id = Some(Uuid::parse_str(&summary.id)
+      .map_err(|e| format!("id of factorgraph {}: {}", &fg.label, e))?);
+    fg.description = summary.description.clone().unwrap_or_default();
+    fg.tags = summary.tags.clone();
+    fg._version = summary.version.clone();
+    fg.createdTimestamp = parse_str_utc(summary.created_timestamp.clone())
+      .map_err(|e| format!("createdTimestamp of factorgraph {}: {}", &fg.label, e))?;
+    fg.lastUpdatedTimestamp = Some(parse_str_utc(summary.last_updated_timestamp.clone())
+      .map_err(|e| format!("lastUpdatedTimestamp of factorgraph {}: {}", &fg.label, e))?);
+    fg.numVariables = summary.num_variables;
+    fg.numFactors = summary.num_factors;
+
+    fg.metadata = full.metadata.clone();
+    fg.solveKeys = Some(full.solve_keys.clone());
+    let mut blob_entries = HashMap::new();
+    for ge in &full.blob_entries {
+      let ne = BlobEntry::from_gql_summary(ge);
+      blob_entries.insert(ne.label.clone(), ne);
+    }
+    fg.blobEntries = Some(blob_entries);
+    fg.agents = Some(full.agents.iter().map(|a| {
+      parse_str_utc(a.last_updated_timestamp.clone())
+        .map(|ts| (a.label.clone(), ts))
+        .map_err(|e| format!("agent {} of factorgraph {}: {}", &a.label, &fg.label, e))
+    }).collect::<Result<Vec<_>, String>>()?);
+    fg.models = Some(full.models.iter().map(|m| {
+      parse_str_utc(m.last_updated_timestamp.clone())
+        .map(|ts| (m.label.clone(), ts))
+        .map_err(|e| format!("model {} of factorgraph {}: {}", &m.label, &fg.label, e))
+    }).collect::<Result<Vec<_>, String>>()?);
+
+    return Ok(fg);
+  }
+}
+
+
+// ===================== QUERIES ========================
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 pub async fn post_list_graphs(
   nvacl: &NavAbilityClient,
@@ -93,6 +178,108 @@ pub fn listGraphs(
 
 
 
+/// Get a factor graph with its metadata, blob entry summaries, and connected agents and models,
+/// `None` if the org has no graph with this label.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_factorgraph(
+  nvacl: &NavAbilityClient,
+  label: &str,
+) -> Result<Option<Factorgraph>, Box<dyn Error>> {
+  
+  let oid = Uuid::parse_str(&nvacl.user_label).expect("cannot parse org_id");
+  let request_body = GetFactorgraph::build_query(get_factorgraph::Variables {
+    fg_id: Uuid::new_v5(&oid, label.as_bytes()).to_string(),
+    full: Some(true),
+  });
+  
+  return post_to_nvaapi::<
+    get_factorgraph::Variables,
+    get_factorgraph::ResponseData,
+    Option<Result<Factorgraph, Box<dyn Error>>>
+  >(
+    nvacl,
+    request_body, 
+    |s| s.factorgraphs.first().map(Factorgraph::from_gql),
+    Some(3)
+  ).await?.transpose();
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn get_factorgraph(
+  nvacl: &NavAbilityClient,
+  label: &str,
+) -> Result<Option<Factorgraph>, Box<dyn Error>> {
+  return crate::execute(post_get_factorgraph(nvacl, label));
+}
+
+
+
+/// Metadata of a factor graph decoded from its base64 JSON encoding.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_factorgraph_metadata(
+  nvacl: &NavAbilityClient,
+  label: &str,
+) -> Result<NodeMetadata, Box<dyn Error>> {
+  let fg = post_get_factorgraph(nvacl, label).await?
+    .ok_or_else(|| Box::new(NotFoundError { details: format!("factorgraph {}", label) }))?;
+  return fg.get_metadata();
+}
+
+
+/// Replace the metadata of a factor graph, returns the stored metadata.  To change single keys, get
+/// the metadata first and `set` or `merge` values.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_set_factorgraph_metadata(
+  nvacl: &NavAbilityClient,
+  label: &str,
+  metadata: &NodeMetadata,
+) -> Result<NodeMetadata, Box<dyn Error>> {
+  
+  let oid = Uuid::parse_str(&nvacl.user_label).expect("cannot parse org_id");
+  let request_body = UpdateFactorgraphMetadata::build_query(update_factorgraph_metadata::Variables {
+    id: Uuid::new_v5(&oid, label.as_bytes()).to_string(),
+    metadata: metadata.to_base64(),
+  });
+  
+  let stored = post_to_nvaapi::<
+    update_factorgraph_metadata::Variables,
+    update_factorgraph_metadata::ResponseData,
+    Option<String>
+  >(
+    nvacl,
+    request_body, 
+    |s| s.update_factorgraphs.factorgraphs.first().map(|fg| fg.metadata.clone().unwrap_or_default()),
+    Some(1)
+  ).await?;
+  
+  return match stored {
+    Some(m) => NodeMetadata::from_base64(&m),
+    None => Err(Box::new(NotFoundError { details: format!("factorgraph {}", label) })),
+  };
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn get_factorgraph_metadata(
+  nvacl: &NavAbilityClient,
+  label: &str,
+) -> Result<NodeMetadata, Box<dyn Error>> {
+  return crate::execute(post_get_factorgraph_metadata(nvacl, label));
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn set_factorgraph_metadata(
+  nvacl: &NavAbilityClient,
+  label: &str,
+  metadata: &NodeMetadata,
+) -> Result<NodeMetadata, Box<dyn Error>> {
+  return crate::execute(post_set_factorgraph_metadata(nvacl, label, metadata));
+}
+
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 pub async fn post_add_factorgraph(
   nvacl: &NavAbilityClient,
@@ -176,10 +363,7 @@ pub async fn post_add_graph_entry(
   if let Some(sz) = entry.size {
     size_s = Some(format!("{}",sz));
   }
-  let mut metadata = entry.metadata.to_string();
-  if metadata.is_empty() {
-    metadata = "e30=".to_string();
-  }
+  let metadata = crate::utils::NodeMetadata::base64_or_empty(&entry.metadata);
   
   let variables = crate::add_factorgraph_blob_entry::Variables {
     fg_label: fg_label.to_string(),
@@ -261,6 +445,256 @@ pub fn connectAgentGraph(
 
 
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_disconnect_graph_agent(
+  nvacl: &NavAbilityClient,
+  graph: &str,
+  agent: &str,
+) -> Result<crate::disconnect_graph_agent::ResponseData, Box<dyn Error>> {
+  
+  let oid = Uuid::parse_str(&nvacl.user_label).expect("cannot parse org_id");
+  let gid = Uuid::new_v5(&oid, graph.as_bytes()).to_string();
+  let aid = Uuid::new_v5(&oid, agent.as_bytes()).to_string();
+  let request_body = DisconnectGraphAgent::build_query(disconnect_graph_agent::Variables {
+    fg_id: gid,
+    agent_id: aid,
+  });
+  
+  return post_to_nvaapi::<
+    disconnect_graph_agent::Variables,
+    disconnect_graph_agent::ResponseData,
+    disconnect_graph_agent::ResponseData
+  >(
+    nvacl,
+    request_body, 
+    |s| s,
+    Some(3)
+  ).await;
+}
+
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn disconnect_agent_graph(
+  nvacl: &NavAbilityClient,
+  graph: &str,
+  agent: &str,
+) -> Result<crate::disconnect_graph_agent::ResponseData, Box<dyn Error>> {
+  return crate::execute(
+    post_disconnect_graph_agent(
+      nvacl,
+      graph,
+      agent
+    )
+  );
+}
+
+
+
+/// Number of variables and factors in a graph, `None` if the graph does not exist.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+async fn post_count_factorgraph_nodes(
+  nvacl: &NavAbilityClient,
+  fg_id: &Uuid,
+) -> Result<Option<(i64, i64)>, Box<dyn Error>> {
+  
+  let request_body = CountFactorgraphNodes::build_query(count_factorgraph_nodes::Variables {
+    fg_id: fg_id.to_string(),
+  });
+  
+  return post_to_nvaapi::<
+    count_factorgraph_nodes::Variables,
+    count_factorgraph_nodes::ResponseData,
+    Option<(i64, i64)>
+  >(
+    nvacl,
+    request_body, 
+    |s| s.factorgraphs.first().map(|fg| (fg.num_variables, fg.num_factors)),
+    Some(3)
+  ).await;
+}
+
+
+/// Delete a factor graph and its blob entries, the blobs themselves are kept.  With `cascade` all
+/// variables and factors of the graph are deleted first, together with their PPEs, solver data
+/// and blob entries, otherwise only an empty graph can be deleted.  Returns the number of nodes
+/// deleted.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_delete_factorgraph(
+  nvacl: &NavAbilityClient,
+  label: &str,
+  cascade: bool,
+) -> Result<i64, Box<dyn Error>> {
+  
+  let oid = Uuid::parse_str(&nvacl.user_label).expect("cannot parse org_id");
+  let fg_id = Uuid::new_v5(&oid, label.as_bytes());
+  
+  let (num_variables, num_factors) = match post_count_factorgraph_nodes(nvacl, &fg_id).await? {
+    Some(counts) => counts,
+    None => return Err(Box::new(NotFoundError {
+      details: format!("factorgraph {}", label),
+    })),
+  };
+  
+  let mut deleted = 0;
+  if cascade {
+    let request_body = DeleteFactorgraphNodes::build_query(delete_factorgraph_nodes::Variables {
+      fg_id: fg_id.to_string(),
+    });
+    deleted += post_to_nvaapi::<
+      delete_factorgraph_nodes::Variables,
+      delete_factorgraph_nodes::ResponseData,
+      i64
+    >(
+      nvacl,
+      request_body, 
+      |s| s.delete_factors.nodes_deleted + s.delete_variables.nodes_deleted,
+      Some(1)
+    ).await?;
+  } else if 0 < num_variables + num_factors {
+    return Err(Box::new(ValidationError {
+      details: format!(
+        "factorgraph {} still has {} variables and {} factors, delete with cascade", 
+        label, num_variables, num_factors
+      ),
+    }));
+  }
+  
+  let request_body = DeleteFactorgraph::build_query(delete_factorgraph::Variables {
+    fg_id: fg_id.to_string(),
+  });
+  deleted += post_to_nvaapi::<
+    delete_factorgraph::Variables,
+    delete_factorgraph::ResponseData,
+    i64
+  >(
+    nvacl,
+    request_body, 
+    |s| s.delete_factorgraphs.nodes_deleted,
+    Some(1)
+  ).await?;
+  
+  return Ok(deleted);
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn delete_factorgraph(
+  nvacl: &NavAbilityClient,
+  label: &str,
+  cascade: bool,
+) -> Result<i64, Box<dyn Error>> {
+  return crate::execute(post_delete_factorgraph(nvacl, label, cascade));
+}
+
+
+
+/// Graph store for a factor graph of the org, without an agent or blob stores.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+fn graph_store(
+  nvacl: &NavAbilityClient,
+  label: &str,
+) -> NavAbilityDFG {
+  let namespace = Uuid::parse_str(&nvacl.user_label).expect("cannot parse org_id");
+  return NavAbilityDFG {
+    client: nvacl.clone(),
+    fg: NvaNode { namespace, label: label.to_owned(), _marker: Default::default() },
+    agent: NvaNode { namespace, label: String::new(), _marker: Default::default() },
+    blobStores: Default::default(),
+  };
+}
+
+
+/// Nodes read from the source graph per request while copying.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+const COPY_PAGE_SIZE: usize = 100;
+
+
+/// Copy a factor graph, with its description and metadata, into a new graph `dst`, which must not
+/// exist yet.  There is no server-side copy, so variables with their PPEs and blob entries, and
+/// then factors, are read from `src` in pages and each page is added to `dst` before the next one
+/// is read, see `import_local_dfg`.  If the copy fails, `dst` is deleted again so it can be
+/// retried.  Blob entries of the graph itself, agent connections and solver data are not copied,
+/// the blobs are shared by both graphs.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_copy_factorgraph(
+  nvacl: &NavAbilityClient,
+  src: &str,
+  dst: &str,
+) -> Result<ImportReport, Box<dyn Error>> {
+  
+  let src_fg = post_get_factorgraph(nvacl, src).await?
+    .ok_or_else(|| Box::new(NotFoundError { details: format!("factorgraph {}", src) }))?;
+  if post_get_factorgraph(nvacl, dst).await?.is_some() {
+    return Err(Box::new(ConflictError {
+      details: format!("factorgraph {} already exists", dst),
+    }));
+  }
+  
+  post_add_factorgraph(
+    nvacl, 
+    dst, 
+    &src_fg.description, 
+    &crate::utils::NodeMetadata::base64_or_empty(&src_fg.metadata.unwrap_or_default())
+  ).await?;
+  let source = graph_store(nvacl, src);
+  let mut target = graph_store(nvacl, dst);
+  let options = ImportOptions {
+    include_blob_entries: true,
+    ..Default::default()
+  };
+
+  let result = async {
+    let mut report = ImportReport::default();
+    for page in source.listVariables().await?.chunks(COPY_PAGE_SIZE) {
+      let mut snapshot = LocalDFG::new(source.fg.namespace, dst);
+      for v in source.getVariables(page).await? {
+        snapshot.variables.insert(v.label.clone(), v);
+      }
+      let added = crate::services::import_local_dfg(&mut target, &snapshot, &options).await?;
+      report.variables.extend(added.variables);
+      report.ppes += added.ppes;
+      report.blob_entries += added.blob_entries;
+      report.solver_data_skipped += added.solver_data_skipped;
+    }
+    for page in source.lsf().await?.chunks(COPY_PAGE_SIZE) {
+      let mut factors = source.getFactors(page).await?;
+      for f in factors.iter_mut() {
+        f.id = None;
+      }
+      let labels: Vec<String> = factors.iter().map(|f| f.label.clone()).collect();
+      target.addFactors(factors).await?;
+      report.factors.extend(labels);
+    }
+    return Ok::<ImportReport, Box<dyn Error>>(report);
+  }.await;
+
+  return match result {
+    Ok(report) => Ok(report),
+    Err(e) => {
+      let cleanup = match post_delete_factorgraph(nvacl, dst, true).await {
+        Ok(_) => format!("{} was deleted again", dst),
+        Err(de) => format!("deleting {} failed too: {}", dst, de),
+      };
+      Err(Box::new(ImportError {
+        details: format!("copying factorgraph {} to {} failed, {}: {}", src, dst, cleanup, e),
+      }))
+    },
+  };
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn copy_factorgraph(
+  nvacl: &NavAbilityClient,
+  src: &str,
+  dst: &str,
+) -> Result<ImportReport, Box<dyn Error>> {
+  return crate::execute(post_copy_factorgraph(nvacl, src, dst));
+}
+
+
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 pub async fn post_find_org_model_fgs(
   nvacl: &NavAbilityClient,
@@ -11,6 +11,8 @@ use chrono::{
   Utc
 };
 
+use serde::{Deserialize, Serialize};
+
 use base64::{
   alphabet,
   engine::{self, general_purpose},
@@ -21,59 +23,337 @@ use base64::{
 use crate::{
   GraphQLQuery,
   GetId,
+  send_api_result,
+};
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::{
   AddFactors,
   add_factors,
-  send_api_result,
 };
 
 use crate::{
   entities::Distributions::Distribution, 
-  FullNormal, 
+  FactorType,
   SDK_VERSION,
   common_traits::GetLabel,
-  entities::Factors::{FactorDFG, FunctionData},
-  // FullNormal, 
+  entities::Factors::{FactorDFG, FunctionData, AnyFactor, ListFactorsFilter},
+  parse_str_utc,
+  to_console_error,
   Point2Point2, 
   Point3Point3, 
   Pose2Pose2, 
+  PartialPose2Pose2,
   Pose3Pose3, 
   PriorPoint2, 
   PriorPoint3,
   PriorPose2, PriorPose3,
+  Pose2Point2Range,
+  Pose2Point2Bearing,
+  Pose2Point2BearingRange,
+  Point2Point2Range,
+  Pose3Point3,
+  ManifoldKernelDensity,
+  Mixture,
+  PackedMixture,
+  PackedCategorical,
+  DistrFactor,
+  services::decode_custom_factor,
+  ConflictError,
+  GQLResponseErrors,
+  Pose2,
+  VariableType,
+  ValidationError,
+  FullNormal,
+  SE2,
+  SE3,
 };
 
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::entities::ClientDFG::NavAbilityDFG;
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::{
+  GetFactor,
+  get_factor,
+  ListFactors,
+  list_factors,
+  DeleteFactor,
+  delete_factor,
+  UpdateFactor,
+  update_factor,
+};
+
 
 // helper macro to avoid repetition of "basic" impl Coordinates
 #[macro_export]
 macro_rules! GenDistrFactor { 
-  ($T:ident) => {
-    impl<'a, D: Distribution<'a>> crate::FactorType<'a, D> for $T<D> {
-      fn new(Z: D) -> Self {
+  ($T:ident, $N:literal) => {
+    impl<D: Distribution> $T<D> {
+      #[allow(non_snake_case)]
+      pub fn new(Z: D) -> Self {
         Self {
           Z
         }
       }
+    }
 
+    impl<D: Distribution> crate::FactorType for $T<D> {
       fn type_str(&self) -> String {
-        return format!("RoME.{}", get_fnc_name(&std::any::type_name::<Self>()));
+        return format!("RoME.{}", stringify!($T));
+      }
+
+      fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+        return Ok(serde_json::json!({
+          "Z": self.Z.to_packed()
+        }));
+      }
+
+      fn arity(&self) -> Option<usize> {
+        return Some($N);
       }
     }
+
+    impl<D: Distribution> crate::DistrFactor for $T<D> {
+      fn packed_distribution(&self) -> crate::PackedDistribution {
+        return self.Z.to_packed();
+      }
+    }
+  }
+}
+
+
+GenDistrFactor!(PriorPoint2, 1);
+GenDistrFactor!(PriorPoint3, 1);
+GenDistrFactor!(PriorPose2, 1);
+GenDistrFactor!(PriorPose3, 1);
+GenDistrFactor!(Point2Point2, 2);
+GenDistrFactor!(Point3Point3, 2);
+GenDistrFactor!(Pose2Pose2, 2);
+GenDistrFactor!(Pose3Pose3, 2);
+GenDistrFactor!(Pose2Point2Range, 2);
+GenDistrFactor!(Pose2Point2Bearing, 2);
+GenDistrFactor!(Point2Point2Range, 2);
+GenDistrFactor!(Pose3Point3, 2);
+
+
+impl Pose2Pose2<FullNormal> {
+  /// Odometry factor from two poses, the measurement is the relative pose `a⁻¹ * b` as `[x, y, θ]`.
+  /// # Arguments
+  /// * `cov` - 3x3 row-major covariance of the relative pose, see `SE2::between_with_cov`.
+  pub fn from_poses(
+    a: &SE2,
+    b: &SE2,
+    cov: Vec<f64>,
+  ) -> Result<Self, Box<dyn Error>> {
+    return Ok(Self::new(FullNormal::new(a.between(b).to_vec(), cov)?));
+  }
+}
+
+impl Pose3Pose3<FullNormal> {
+  /// Odometry factor from two poses, the measurement is the relative pose `a⁻¹ * b` as translation
+  /// and rotation vector `[x, y, z, ωx, ωy, ωz]`.
+  /// # Arguments
+  /// * `cov` - 6x6 row-major covariance of the relative pose, see `SE3::between_with_cov`.
+  pub fn from_poses(
+    a: &SE3,
+    b: &SE3,
+    cov: Vec<f64>,
+  ) -> Result<Self, Box<dyn Error>> {
+    return Ok(Self::new(FullNormal::new(a.between(b).to_vec(), cov)?));
+  }
+}
+
+impl<B: Distribution, R: Distribution> Pose2Point2BearingRange<B, R> {
+  pub fn new(
+    bearing: B,
+    range: R,
+  ) -> Self {
+    Self {
+      bearing,
+      range,
+    }
+  }
+}
+
+impl<B: Distribution, R: Distribution> crate::FactorType for Pose2Point2BearingRange<B, R> {
+  fn type_str(&self) -> String {
+    return "RoME.Pose2Point2BearingRange".to_owned();
+  }
+
+  fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+    return Ok(serde_json::json!({
+      "bearstr": self.bearing.to_packed(),
+      "rangstr": self.range.to_packed(),
+    }));
+  }
+
+  fn arity(&self) -> Option<usize> {
+    return Some(2);
+  }
+}
+
+
+impl<F: FactorType> Mixture<F> {
+  /// Create a new Mixture factor with one weight per component, the weights are normalized to sum to one.
+  pub fn new(
+    components: Vec<F>,
+    weights: Vec<f64>,
+  ) -> Result<Self, Box<dyn Error>> {
+    if components.is_empty() || components.len() != weights.len() {
+      return Err(Box::new(ValidationError {
+        details: format!("Mixture requires one weight per component, got {} components and {} weights", components.len(), weights.len()),
+      }));
+    }
+    if let Some(i) = weights.iter().position(|w| !(0.0 <= *w && w.is_finite())) {
+      return Err(Box::new(ValidationError {
+        details: format!("Mixture requires finite non-negative weights, got weights[{}]={}", i, weights[i]),
+      }));
+    }
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+      return Err(Box::new(ValidationError {
+        details: "Mixture weights must not all be zero".to_owned(),
+      }));
+    }
+    return Ok(Self {
+      components,
+      weights: weights.iter().map(|w| w / total).collect(),
+    });
+  }
+
+  pub fn components(&self) -> &[F] {
+    return &self.components;
+  }
+
+  /// normalized weights, one per component
+  pub fn weights(&self) -> &[f64] {
+    return &self.weights;
+  }
+}
+
+impl<F: DistrFactor> Mixture<F> {
+  pub fn to_packed(
+    &self
+  ) -> PackedMixture {
+    return PackedMixture {
+      N: self.components.len() as i64,
+      F_: self.components[0].type_str(),
+      S: (1..=self.components.len()).map(|i| format!("c{}", i)).collect(),
+      components: self.components.iter().map(|c| c.packed_distribution()).collect(),
+      diversity: PackedCategorical {
+        type_: "IncrementalInference.PackedCategorical".to_owned(),
+        p: self.weights.clone(),
+      },
+    };
+  }
+}
+
+impl<F: DistrFactor> FactorType for Mixture<F> {
+  fn type_str(&self) -> String {
+    return "IncrementalInference.Mixture".to_owned();
+  }
+
+  fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+    return self.to_packed().packed_fnc();
+  }
+
+  fn arity(&self) -> Option<usize> {
+    return self.components.first().and_then(|c| c.arity());
+  }
+}
+
+impl FactorType for PackedMixture {
+  fn type_str(&self) -> String {
+    return "IncrementalInference.Mixture".to_owned();
+  }
+
+  fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+    return Ok(serde_json::to_value(self)?);
   }
 }
 
 
-GenDistrFactor!(PriorPoint2);
-GenDistrFactor!(PriorPoint3);
-GenDistrFactor!(PriorPose2);
-GenDistrFactor!(PriorPose3);
-GenDistrFactor!(Point2Point2);
-GenDistrFactor!(Point3Point3);
-GenDistrFactor!(Pose2Pose2);
-GenDistrFactor!(Pose3Pose3);
+impl Pose2Pose2<ManifoldKernelDensity> {
+  /// Create a Pose2Pose2 factor from a partial measurement, e.g. only the (x,y) translation
+  /// without heading, see `ManifoldKernelDensity::new_partial`.
+  #[allow(non_snake_case)]
+  pub fn new_partial(
+    Z: ManifoldKernelDensity,
+  ) -> Result<Self, Box<dyn Error>> {
+    if Z.varType != Pose2.type_str() || Z.partial.is_empty() {
+      return Err(Box::new(ValidationError {
+        details: format!("Pose2Pose2::new_partial requires a partial {} ManifoldKernelDensity, got {} with partial={:?}", Pose2.type_str(), Z.varType, Z.partial),
+      }));
+    }
+    return Ok(Self {
+      Z
+    });
+  }
+}
+
+
+impl<D: Distribution> PartialPose2Pose2<D> {
+  /// Create a partial Pose2Pose2 factor, `partial` lists the zero-based (x,y,th) coordinates measured by `Z`,
+  /// e.g. `PartialPose2Pose2::new(FullNormal::new(vec![1.0, 0.0], vec![0.01, 0.0, 0.0, 0.01])?, vec![0, 1])`.
+  #[allow(non_snake_case)]
+  pub fn new(
+    Z: D,
+    partial: Vec<usize>,
+  ) -> Result<Self, Box<dyn Error>> {
+    if partial.is_empty() || Z.dim() != partial.len() {
+      return Err(Box::new(ValidationError {
+        details: format!("PartialPose2Pose2 measurement has dimension {} but partial={:?}", Z.dim(), partial),
+      }));
+    }
+    for (i, p) in partial.iter().enumerate() {
+      if Pose2.dim() <= *p || partial[..i].contains(p) {
+        return Err(Box::new(ValidationError {
+          details: format!("PartialPose2Pose2 partial={:?} has invalid or repeated coordinate {}", partial, p),
+        }));
+      }
+    }
+    return Ok(Self {
+      Z,
+      partial,
+    });
+  }
+
+  #[allow(non_snake_case)]
+  pub fn Z(&self) -> &D {
+    return &self.Z;
+  }
+
+  /// zero-based (x,y,th) coordinates measured by `Z`
+  pub fn partial(&self) -> &[usize] {
+    return &self.partial;
+  }
+}
+
+impl<D: Distribution> FactorType for PartialPose2Pose2<D> {
+  fn type_str(&self) -> String {
+    return "RoME.Pose2Pose2".to_owned();
+  }
+
+  /// Packed like `Pose2Pose2` with the one-based `partial` coordinates as used in Julia.
+  fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+    return Ok(serde_json::json!({
+      "Z": self.Z.to_packed(),
+      "partial": self.partial.iter().map(|p| p + 1).collect::<Vec<usize>>(),
+    }));
+  }
+
+  fn arity(&self) -> Option<usize> {
+    return Some(2);
+  }
+}
+
+impl<D: Distribution> crate::DistrFactor for PartialPose2Pose2<D> {
+  fn packed_distribution(&self) -> crate::PackedDistribution {
+    return self.Z.to_packed();
+  }
+}
 
 
 
@@ -91,6 +371,24 @@ fn assemble_factor_name(ovlb: Vec<String>) -> String {
   return flb;
 }
 
+/// Same as `assemble_factor_name` but the suffix is a hash of the variable labels, factor type and
+/// a caller provided sequence key, so that the same factor always gets the same label.
+fn assemble_factor_name_deterministic(
+  ovlb: &Vec<String>,
+  fnctype: &str,
+  seqkey: &str,
+) -> String {
+  let mut flb = "".to_string();
+  for o in ovlb {
+    flb += o;
+  }
+  let key = format!("{}|{}|{}", ovlb.join(","), fnctype, seqkey);
+  flb += "_";
+  flb += &(Uuid::new_v5(&Uuid::NAMESPACE_OID, key.as_bytes()).simple().to_string()[0..8]);
+
+  return flb;
+}
+
 fn get_fnc_name(fnc: &str) -> String {
   let parts = fnc.split(".");
   let mut t = "";
@@ -102,14 +400,16 @@ fn get_fnc_name(fnc: &str) -> String {
 
 
 impl FunctionData {
+  /// # Arguments
+  /// * `fnc` - the packed factor type, see `FactorType::packed_fnc`.
   pub fn new(
-    fnc: &str,
+    fnc: serde_json::Value,
     multihypo: Option<Vec<f64>>,
     nullhypo: Option<f64>,
     inflation: Option<f64>,
   ) -> Self {
     let mut fd = Self::default();
-    fd.fnc = fnc.to_owned();
+    fd.fnc = fnc;
     fd.nullhypo = nullhypo.unwrap_or(0.0);
     fd.multihypo = multihypo.unwrap_or(Vec::new());
     fd.inflation = inflation.unwrap_or(3.0);
@@ -119,6 +419,12 @@ impl FunctionData {
   pub fn to_json(&self) -> String {
     serde_json::to_string(self).unwrap().to_string()
   }
+
+  pub fn from_json(
+    data: &str
+  ) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(data)
+  }
   
   pub fn to_string_b64(&self) -> String {
     general_purpose::STANDARD.encode(self.to_json())
@@ -126,11 +432,202 @@ impl FunctionData {
 }
 
 
-// TODO support more D: Distributions<'a>
-impl<'a, F> FactorDFG<F> 
-where 
-  F: crate::FactorType<'a, FullNormal<'a>>
-{
+/// Intermediate packed layout for (de)serializing `AnyFactor`.
+#[derive(Serialize, Deserialize)]
+struct PackedFactorParts {
+  fnctype: String,
+  fnc: serde_json::Value,
+}
+
+
+// helper macro to avoid repetition over all known AnyFactor variants
+macro_rules! implAnyFactor { 
+  ($($T:ident),*) => {
+    impl AnyFactor {
+      /// Assemble from a fnctype string, e.g. "RoME.Pose2Pose2" or "Pose2Pose2", and the JSON of the packed factor.
+      /// Registered custom factor types take precedence, see `register_factor_type`.
+      /// Falls back to `AnyFactor::Unknown` if the type is not supported or the JSON does not match.
+      pub fn from_parts(
+        fnctype: &str,
+        fnc: &str,
+      ) -> Self {
+        if let Some(custom) = decode_custom_factor(fnctype, fnc) {
+          return AnyFactor::Custom(custom);
+        }
+        let known = match get_fnc_name(fnctype).as_str() {
+          $(
+            stringify!($T) => serde_json::from_str(fnc).map(AnyFactor::$T).ok(),
+          )*
+          _ => None,
+        };
+        return known.unwrap_or(AnyFactor::Unknown {
+          fnctype: fnctype.to_owned(),
+          raw_json: fnc.to_owned(),
+        });
+      }
+
+      /// JSON of the packed factor, i.e. the `fnc` part.
+      pub fn fnc_json(&self) -> Result<String, Box<dyn Error>> {
+        if let AnyFactor::Unknown { raw_json, .. } = self {
+          return Ok(raw_json.to_owned());
+        }
+        return Ok(self.packed_fnc()?.to_string());
+      }
+    }
+
+    impl FactorType for AnyFactor {
+      /// Fully qualified factor type, e.g. "RoME.Pose2Pose2".
+      fn type_str(&self) -> String {
+        match self {
+          AnyFactor::Unknown { fnctype, .. } => fnctype.to_owned(),
+          AnyFactor::Custom(f) => f.type_str(),
+          $(
+            AnyFactor::$T(f) => f.type_str(),
+          )*
+        }
+      }
+
+      fn packed_fnc(&self) -> Result<serde_json::Value, Box<dyn Error>> {
+        match self {
+          AnyFactor::Unknown { raw_json, .. } => {
+            return Ok(serde_json::from_str(raw_json)
+              .unwrap_or(serde_json::Value::String(raw_json.to_owned())));
+          },
+          AnyFactor::Custom(f) => f.packed_fnc(),
+          $(
+            AnyFactor::$T(f) => f.packed_fnc(),
+          )*
+        }
+      }
+
+      fn arity(&self) -> Option<usize> {
+        match self {
+          AnyFactor::Unknown { .. } => None,
+          AnyFactor::Custom(f) => f.arity(),
+          $(
+            AnyFactor::$T(f) => f.arity(),
+          )*
+        }
+      }
+    }
+  }
+}
+
+
+implAnyFactor!(
+  PriorPoint2,
+  PriorPoint3,
+  PriorPose2,
+  PriorPose3,
+  Point2Point2,
+  Point3Point3,
+  Pose2Pose2,
+  Pose3Pose3,
+  Pose2Point2Range,
+  Pose2Point2Bearing,
+  Pose2Point2BearingRange,
+  Point2Point2Range,
+  Pose3Point3,
+  Mixture
+);
+
+
+impl PartialEq for AnyFactor {
+  /// Factors are equal if they pack to the same type and payload.
+  fn eq(&self, other: &Self) -> bool {
+    return self.type_str() == other.type_str() && match (self.packed_fnc(), other.packed_fnc()) {
+      (Ok(a), Ok(b)) => same_packed_values(&a, &b),
+      _ => false,
+    };
+  }
+}
+
+
+/// True if two packed payloads hold the same values, numbers are compared as floats so
+/// that `0` and `0.0` from different serializers are the same.
+fn same_packed_values(
+  a: &serde_json::Value,
+  b: &serde_json::Value,
+) -> bool {
+  use serde_json::Value;
+  return match (a, b) {
+    (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+    (Value::Array(x), Value::Array(y)) => {
+      x.len() == y.len() && x.iter().zip(y).all(|(x, y)| same_packed_values(x, y))
+    },
+    (Value::Object(x), Value::Object(y)) => {
+      x.len() == y.len() && x.iter().all(|(k, v)| y.get(k).map_or(false, |w| same_packed_values(v, w)))
+    },
+    _ => a == b,
+  };
+}
+
+
+impl Serialize for AnyFactor {
+  fn serialize<S: serde::Serializer>(
+    &self, 
+    serializer: S
+  ) -> Result<S::Ok, S::Error> {
+    return PackedFactorParts {
+      fnctype: self.type_str(),
+      fnc: self.packed_fnc().map_err(serde::ser::Error::custom)?,
+    }.serialize(serializer);
+  }
+}
+
+
+impl<'de> Deserialize<'de> for AnyFactor {
+  fn deserialize<De: serde::Deserializer<'de>>(
+    deserializer: De
+  ) -> Result<Self, De::Error> {
+    let parts = PackedFactorParts::deserialize(deserializer)?;
+    let fnc = match parts.fnc {
+      serde_json::Value::String(raw) => raw,
+      value => value.to_string(),
+    };
+    return Ok(AnyFactor::from_parts(&parts.fnctype, &fnc));
+  }
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+impl FactorDFG<AnyFactor> {
+  pub fn from_gql(
+    fgql: &get_factor::GetFactorFactors
+  ) -> Self {
+    let data = match FunctionData::from_json(&fgql.factor_full_fields.data) {
+      Ok(fd) => Some(fd),
+      Err(e) => {
+        to_console_error(&format!("Unable to decode factor {} data: {:?}", &fgql.factor_skeleton_fields.label, e));
+        None
+      }
+    };
+    let fnc = match data.as_ref().map(|fd| &fd.fnc) {
+      // older clients stored the packed factor as a JSON string
+      Some(serde_json::Value::String(s)) => s.clone(),
+      Some(v) => v.to_string(),
+      None => String::new(),
+    };
+
+    return Self {
+      id: Uuid::parse_str(&fgql.factor_skeleton_fields.id).ok(),
+      label: fgql.factor_skeleton_fields.label.clone(),
+      tags: fgql.factor_skeleton_fields.tags.clone(),
+      variableOrderSymbols_: fgql.factor_skeleton_fields.variable_order_symbols.clone(),
+      timestamp: parse_str_utc(fgql.factor_summary_fields.timestamp.clone()).ok(),
+      nstime: Some(fgql.factor_summary_fields.nstime.clone()),
+      fnctype: AnyFactor::from_parts(&fgql.factor_full_fields.fnctype, &fnc),
+      solvable: Some(fgql.factor_full_fields.solvable),
+      data,
+      metadata: fgql.factor_full_fields.metadata.clone(),
+      _version: Some(fgql.factor_full_fields.version.clone()),
+      lastUpdatedTimestamp: parse_str_utc(fgql.factor_summary_fields.last_updated_timestamp.clone()).ok(),
+    }
+  }
+}
+
+
+impl<F: FactorType> FactorDFG<F> {
   /// Create a new factor
   /// # Arguments
   /// * `varlbls` - variable labels is a ordered vector of variable label strings, e.g. ["x1", "x2"]
@@ -139,17 +636,17 @@ where
   /// * `timestamp` - timestamp, in DateTime<Utc>, default is now
   /// * `nstime` - nstime, also known as "time since epoch" in nanoseconds
   /// * # Returns
-  /// * `FactorDFG` - a new factor
+  /// * `Result<FactorDFG, _>` - a new factor, error if the factor type can not be packed
   /// # Example
   /// ```
   /// use navabilitysdk::services::Factors;
   /// use chrono::{DateTime, Utc};
   /// let f = Factors::new(
   ///   vec!["x1", "x2"], 
-  ///   Pose2Pose2::new(FullNormal::new(vec![&[1.0, 2.0, 3.0], &[0.01, 0.01, 0.01]])),
+  ///   Pose2Pose2::new(FullNormal::new(vec![1.0, 2.0, 3.0], vec![0.01, 0.0, 0.0, 0.0, 0.01, 0.0, 0.0, 0.0, 0.01])?),
   ///   vec!["ODOMETRY","BODY_MOTION"], 
   ///   Some(Utc::now())
-  /// );
+  /// )?;
   /// ```
   /// # Note
   /// * This is a simplified version of the ::new_more function, which has more options.
@@ -159,7 +656,7 @@ where
     tags: Vec<String>,
     timestamp: Option<DateTime<Utc>>,
     nstime: Option<usize>,
-  ) -> Self {
+  ) -> Result<Self, Box<dyn Error>> {
     return Self::new_more(
       varlbls, 
       fnctype, 
@@ -180,7 +677,7 @@ where
   /// nullhypo - nullhypo, default is 0.0.  Allows fractional null hypotheses, see Caesar.jl Docs for details
   /// inflation - inflation, default is 3.0.  Allows inflation of the covariance, see Caesar.jl Docs for details
   /// # Returns
-  /// * `FactorDFG` - a new factor
+  /// * `Result<FactorDFG, _>` - a new factor, error if the factor type can not be packed
   pub fn new_more(
     varlbls: Vec<String>,
     fnctype: F,
@@ -191,7 +688,8 @@ where
     multihypo: Option<Vec<f64>>,
     nullhypo: Option<f64>,
     inflation: Option<f64>,
-  ) -> Self {
+  ) -> Result<Self, Box<dyn Error>> {
+    let fnc = fnctype.packed_fnc()?;
     let mut f = Self {
       id: None,
       label: assemble_factor_name(varlbls.clone()),
@@ -202,8 +700,9 @@ where
       fnctype,
       solvable: Some(solvable.unwrap_or(0)),
       data: None,
-      metadata: Some("e30=".to_string()),
+      metadata: Some(crate::utils::NodeMetadata::new().to_base64()),
       _version: Some(crate::SDK_VERSION.to_string()),
+      lastUpdatedTimestamp: None,
     };
     
     for vl in varlbls {
@@ -216,39 +715,192 @@ where
       }
     }
     // default on create, also deser is different use-case    
-    let fdata = FunctionData::new("FIXME", multihypo, nullhypo, inflation);
-    // FIXME, should not be json'd so early: JuliaRobotics/DistributedFactorGraphs.jl#1118
-    f.data = Some(fdata.to_json());
+    let mut fdata = FunctionData::new(fnc, multihypo, nullhypo, inflation);
+    // 1-based positions of the certain variables, as IncrementalInference packs them
+    fdata.certainhypo = (1..=f.variableOrderSymbols_.len() as i64)
+      .filter(|i| fdata.multihypo.get(*i as usize - 1).map_or(true, |h| *h == 0.0 || *h == 1.0))
+      .collect();
+    f.data = Some(fdata);
     
-    return f;
+    return Ok(f);
+  }
+
+  /// Create a new multihypothesis factor, e.g. for loop closures with ambiguous data association.
+  /// # Arguments
+  /// * `multihypo` - one entry per variable in `varlbls`, 1.0 for variables that are certain and
+  ///   fractions for the ambiguous variables which must sum to 1, e.g. `[1.0, 0.5, 0.5]`.
+  /// * `nullhypo` - optional fraction in [0,1) that the measurement is an outlier.
+  /// # Returns
+  /// * `Result<FactorDFG, _>` - error if the hypotheses do not match the variables, see `validate_hypotheses`.
+  pub fn new_multihypo(
+    varlbls: Vec<String>,
+    fnctype: F,
+    tags: Vec<String>,
+    timestamp: Option<DateTime<Utc>>,
+    nstime: Option<usize>,
+    multihypo: Vec<f64>,
+    nullhypo: Option<f64>,
+  ) -> Result<Self, Box<dyn Error>> {
+    let f = Self::new_more(
+      varlbls, 
+      fnctype, 
+      tags, 
+      timestamp, 
+      nstime, 
+      None, 
+      Some(multihypo), 
+      nullhypo, 
+      None,
+    )?;
+    f.validate()?;
+    return Ok(f);
+  }
+
+  /// Replace the random label suffix with a deterministic hash of the variable labels, factor type
+  /// and `seqkey`, e.g. a sensor message sequence number.  Adding the same factor again is then
+  /// idempotent, see `post_add_factor`.
+  pub fn with_deterministic_label(
+    mut self,
+    seqkey: &str,
+  ) -> Self {
+    self.label = assemble_factor_name_deterministic(
+      &self.variableOrderSymbols_, 
+      &self.fnctype.type_str(), 
+      seqkey
+    );
+    return self;
+  }
+
+  /// Check whether `other` describes the same factor, i.e. same type, variables and function data,
+  /// ignoring bookkeeping fields such as id, tags and timestamps.  The measurements are compared
+  /// decoded, so differences in JSON formatting such as `0` vs `0.0` do not matter.
+  pub fn is_same_factor<G: FactorType>(
+    &self,
+    other: &FactorDFG<G>,
+  ) -> bool {
+    let same_data = match (&self.data, &other.data) {
+      (Some(a), Some(b)) => {
+        AnyFactor::from_parts(&self.fnctype.type_str(), &a.fnc.to_string())
+          == AnyFactor::from_parts(&self.fnctype.type_str(), &b.fnc.to_string())
+          && a.multihypo == b.multihypo
+          && a.nullhypo == b.nullhypo
+      },
+      (None, None) => true,
+      _ => false,
+    };
+    return self.label == other.label
+      && get_fnc_name(&self.fnctype.type_str()) == get_fnc_name(&other.fnctype.type_str())
+      && self.variableOrderSymbols_ == other.variableOrderSymbols_
+      && same_data;
+  }
+
+  /// Type-erased copy of this factor, e.g. to store factors of different types together.
+  pub fn to_any(&self) -> Result<FactorDFG<AnyFactor>, Box<dyn Error>> {
+    return Ok(FactorDFG {
+      id: self.id,
+      label: self.label.clone(),
+      tags: self.tags.clone(),
+      variableOrderSymbols_: self.variableOrderSymbols_.clone(),
+      timestamp: self.timestamp,
+      nstime: self.nstime.clone(),
+      fnctype: AnyFactor::from_parts(&self.fnctype.type_str(), &self.fnctype.packed_fnc()?.to_string()),
+      solvable: self.solvable,
+      data: self.data.clone(),
+      metadata: self.metadata.clone(),
+      _version: self._version.clone(),
+      lastUpdatedTimestamp: self.lastUpdatedTimestamp,
+    });
+  }
+
+  /// Check the factor type arity and hypotheses against the connected variables.
+  pub fn validate(
+    &self
+  ) -> Result<(), Box<dyn Error>> {
+    self.validate_hypotheses()?;
+    if let Some(arity) = self.fnctype.arity() {
+      // with multihypo the ambiguous variables share one slot of the factor type
+      let slots = match self.data.as_ref().map(|fd| &fd.multihypo) {
+        Some(mh) if !mh.is_empty() => {
+          let certain = mh.iter().filter(|h| **h == 0.0 || **h == 1.0).count();
+          certain + usize::from(certain < mh.len())
+        },
+        _ => self.variableOrderSymbols_.len(),
+      };
+      if arity != slots {
+        let multihypo = match self.data.as_ref().map(|fd| &fd.multihypo) {
+          Some(mh) if !mh.is_empty() => format!(" (multihypo={:?} over {} variables)", mh, self.variableOrderSymbols_.len()),
+          _ => "".to_owned(),
+        };
+        return Err(Box::new(ValidationError {
+          details: format!("Factor {} of type {} connects {} variable slots{} but requires {}", self.label, self.fnctype.type_str(), slots, multihypo, arity),
+        }));
+      }
+    }
+    return Ok(());
+  }
+
+  /// Check `multihypo` and `nullhypo` in the factor data against the connected variables.
+  pub fn validate_hypotheses(
+    &self
+  ) -> Result<(), Box<dyn Error>> {
+    let fdata = match &self.data {
+      Some(fd) => fd,
+      None => return Ok(()),
+    };
+    if !(0.0 <= fdata.nullhypo && fdata.nullhypo < 1.0) {
+      return Err(Box::new(ValidationError {
+        details: format!("Factor {} nullhypo={} must be in [0,1)", self.label, fdata.nullhypo),
+      }));
+    }
+    let mh = &fdata.multihypo;
+    if mh.is_empty() {
+      return Ok(());
+    }
+    if mh.len() != self.variableOrderSymbols_.len() {
+      return Err(Box::new(ValidationError {
+        details: format!("Factor {} multihypo has {} entries but connects {} variables", self.label, mh.len(), self.variableOrderSymbols_.len()),
+      }));
+    }
+    // 1.0 marks a certain variable, as does 0.0 in the packed data written by IncrementalInference,
+    // the fractions of the uncertain variables must sum to 1
+    if let Some(i) = mh.iter().position(|h| !(0.0 <= *h && *h <= 1.0)) {
+      return Err(Box::new(ValidationError {
+        details: format!("Factor {} multihypo[{}]={} must be in [0,1]", self.label, i, mh[i]),
+      }));
+    }
+    let fractions: Vec<f64> = mh.iter().cloned().filter(|h| 0.0 < *h && *h < 1.0).collect();
+    let total: f64 = fractions.iter().sum();
+    if 1e-6 < (total - 1.0).abs() {
+      return Err(Box::new(ValidationError {
+        details: format!("Factor {} multihypo fractions {:?} sum to {} instead of 1", self.label, fractions, total),
+      }));
+    }
+    return Ok(());
   }
 }
 
 
+/// Assemble the API input to create a factor, the id is derived from the label.
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
-pub async fn post_add_factor<'a, F: crate::FactorType<'a, FullNormal<'a>>>(
+fn factor_create_input<F: FactorType>(
   nvafg: &NavAbilityDFG,
-  factor: FactorDFG<F>,
-) -> Result<add_factors::ResponseData, Box<dyn crate::Error>> {
-
-  let label = factor.getLabel().to_string();
-  let id = nvafg.getId(&label).to_string();
-
+  factor: &FactorDFG<F>,
+) -> add_factors::FactorCreateInput {
   let mut variable_order_symbols = Vec::new();
-  for v in factor.variableOrderSymbols_ {
-    variable_order_symbols.push(Some(v));
+  for v in &factor.variableOrderSymbols_ {
+    variable_order_symbols.push(Some(v.clone()));
   }
 
-  let newfac = add_factors::FactorCreateInput {
-    id,
-    label,
-    tags: factor.tags,
+  return add_factors::FactorCreateInput {
+    id: nvafg.getId(&factor.label).to_string(),
+    label: factor.label.clone(),
+    tags: factor.tags.clone(),
     timestamp: factor.timestamp.expect("FactorDFG missing .timestamp field").to_string(),
-    nstime: factor.nstime.expect("FactorDFG missing .nstime field"),
+    nstime: factor.nstime.clone().expect("FactorDFG missing .nstime field"),
     fnctype: factor.fnctype.type_str(),
     solvable: factor.solvable.expect("FactorDFG missing .solvable field"),
-    data: factor.data.expect("FactorDFG missing .data field"),
-    metadata: factor.metadata,
+    data: factor.data.as_ref().expect("FactorDFG missing .data field").to_json(),
+    metadata: factor.metadata.clone(),
     variable_order_symbols: Some(variable_order_symbols),
     version: SDK_VERSION.to_string(),
     blob_entries: None,
@@ -256,38 +908,121 @@ pub async fn post_add_factor<'a, F: crate::FactorType<'a, FullNormal<'a>>>(
     variables: None,
     // _type: "",
   };
+}
 
+
+/// Send factors to the API in a single request, returns their ids in order.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+async fn send_add_factors(
+  nvafg: &NavAbilityDFG,
+  factors_to_create: Vec<add_factors::FactorCreateInput>,
+) -> Result<Vec<Uuid>, Box<dyn crate::Error>> {
   let request_body = AddFactors::build_query(
     add_factors::Variables {
-        factors_to_create: vec![newfac],
+        factors_to_create,
     }
   );
-  
+
   return crate::post_to_nvaapi::<
     add_factors::Variables,
     add_factors::ResponseData,
-    add_factors::ResponseData
+    Vec<Uuid>
   >(
     &nvafg.client,
-    request_body, 
-    |s| s,
+    request_body,
+    |s| {
+      return s.add_factors.factors.iter()
+        .map(|f| Uuid::parse_str(&f.factor_skeleton_fields.id).expect("post_add_factors not able to parse uuid from API response"))
+        .collect();
+    },
     Some(1)
   ).await;
 }
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+fn is_duplicate_error(
+  err: &Box<dyn crate::Error>,
+) -> bool {
+  return err.downcast_ref::<GQLResponseErrors>().map_or(false, |e| e.is_duplicate());
+}
+
+
+/// Add factors to the graph, behaves as upsert-or-conflict: factor ids are derived from the
+/// label so the API rejects a factor that already exists.  Each factor that already exists and
+/// is the same factor (see `FactorDFG::is_same_factor`) keeps its id without creating a
+/// duplicate, otherwise a `ConflictError` is returned.  Existing factors are only read when
+/// the API reports a duplicate.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_add_factors<F: FactorType>(
+  nvafg: &NavAbilityDFG,
+  factors: Vec<FactorDFG<F>>,
+) -> Result<Vec<Uuid>, Box<dyn crate::Error>> {
+
+  for factor in &factors {
+    factor.validate()?;
+  }
+
+  let inputs = factors.iter().map(|f| factor_create_input(nvafg, f)).collect();
+  let err = match send_add_factors(nvafg, inputs).await {
+    Ok(new_ids) => return Ok(new_ids),
+    Err(e) if is_duplicate_error(&e) => e,
+    Err(e) => return Err(e),
+  };
+
+  // the request was rejected as a whole, keep the factors that already exist and send the rest
+  let mut ids: Vec<Option<Uuid>> = vec![None; factors.len()];
+  let mut missing = Vec::new();
+  for (i, factor) in factors.iter().enumerate() {
+    let label = factor.getLabel();
+    match post_get_factor(nvafg, label).await? {
+      Some(existing) if factor.is_same_factor(&existing) => {
+        ids[i] = Some(existing.id.unwrap_or(nvafg.getId(label)));
+      },
+      Some(_) => {
+        return Err(Box::new(ConflictError {
+          details: format!("factor {} already exists with a different type, variables or data", label),
+        }));
+      },
+      None => missing.push(i),
+    }
+  }
+  if missing.len() == factors.len() {
+    return Err(err);
+  }
+  if !missing.is_empty() {
+    let inputs = missing.iter().map(|i| factor_create_input(nvafg, &factors[*i])).collect();
+    let new_ids = send_add_factors(nvafg, inputs).await?;
+    for (i, id) in missing.iter().zip(new_ids) {
+      ids[*i] = Some(id);
+    }
+  }
+  return Ok(ids.into_iter().map(|id| id.expect("every factor is either added or existing")).collect());
+}
+
+
+/// Add a factor to the graph, see `post_add_factors` for how existing factors are handled.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_add_factor<F: FactorType>(
+  nvafg: &NavAbilityDFG,
+  factor: FactorDFG<F>,
+) -> Result<Uuid, Box<dyn crate::Error>> {
+  let ids = post_add_factors(nvafg, vec![factor]).await?;
+  return ids.into_iter().next().ok_or_else(|| "post_add_factor API response has no factor".into());
+}
+
 
 #[cfg(any(feature = "tokio", feature = "thread"))]
-pub fn addFactor<'a, F: crate::FactorType<'a, FullNormal<'a>>>(
+pub fn addFactor<F: FactorType>(
   nvafg: &NavAbilityDFG,
   factor: FactorDFG<F>,
-) -> Result<add_factors::ResponseData, Box<dyn Error>> {
+) -> Result<Uuid, Box<dyn Error>> {
   return crate::execute(post_add_factor(nvafg, factor));
 }
 
 
 #[cfg(any(feature = "tokio", feature = "thread"))] // feature = "thread", 
-pub fn q_addFactor<'a, F: crate::FactorType<'a, FullNormal<'a>>>(
-  send_into: Sender<add_factors::ResponseData>, 
+pub fn q_addFactor<F: FactorType>(
+  send_into: Sender<Uuid>, 
   nvafg: NavAbilityDFG,
   factor: FactorDFG<F>,
 ) -> Result<(), Box<dyn Error>> {
@@ -299,3 +1034,164 @@ pub fn q_addFactor<'a, F: crate::FactorType<'a, FullNormal<'a>>>(
   })
 }
 
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_factor(
+  nvafg: &NavAbilityDFG,
+  label: &str,
+) -> Result<Option<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+  let factors = post_get_factors(nvafg, &[label.to_owned()]).await?;
+  return Ok(factors.into_iter().next());
+}
+
+
+/// Get several factors in one request, labels not in the graph are left out of the result.
+/// Order of the result is not guaranteed, keep pages to a few hundred labels.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_factors(
+  nvafg: &NavAbilityDFG,
+  labels: &[String],
+) -> Result<Vec<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+
+  let request_body = GetFactor::build_query(
+    get_factor::Variables {
+      factor_ids: labels.iter().map(|l| nvafg.getId(l).to_string()).collect(),
+      fields_summary: true,
+      fields_full: true,
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    get_factor::Variables,
+    get_factor::ResponseData,
+    Vec<FactorDFG<AnyFactor>>
+  >(
+    &nvafg.client,
+    request_body, 
+    |s| s.factors.iter().map(FactorDFG::from_gql).collect(),
+    Some(3)
+  ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_list_factors(
+  nvafg: &NavAbilityDFG,
+  filter: &ListFactorsFilter,
+) -> Result<Vec<String>, Box<dyn Error>> {
+
+  let request_body = ListFactors::build_query(
+    list_factors::Variables {
+      fg_id: nvafg.fg.getId("").to_string(),
+      solvable_gt: None,
+      solvable_gte: filter.solvable,
+      solvable_in: None,
+      solvable_lt: None,
+      solvable_lte: None,
+      tags_includes: filter.tag.clone(),
+      fnctype: filter.fnctype.clone(),
+      variable_order_symbols_includes: filter.variable.clone(),
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    list_factors::Variables,
+    list_factors::ResponseData,
+    Vec<String>
+  >(
+    &nvafg.client,
+    request_body, 
+    |s| s.list_factors,
+    Some(3)
+  ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_delete_factor(
+  nvafg: &NavAbilityDFG,
+  label: &str,
+) -> Result<delete_factor::ResponseData, Box<dyn Error>> {
+
+  let request_body = DeleteFactor::build_query(
+    delete_factor::Variables {
+      factor_id: nvafg.getId(label).to_string(),
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    delete_factor::Variables,
+    delete_factor::ResponseData,
+    delete_factor::ResponseData
+  >(
+    &nvafg.client,
+    request_body, 
+    |s| s,
+    Some(1)
+  ).await;
+}
+
+
+/// Update the type, data, tags, solvable, timestamps and metadata of an existing factor,
+/// the connected variables cannot be changed.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_update_factor<F: FactorType>(
+  nvafg: &NavAbilityDFG,
+  factor: &FactorDFG<F>,
+) -> Result<Uuid, Box<dyn Error>> {
+
+  factor.validate()?;
+
+  let request_body = UpdateFactor::build_query(
+    update_factor::Variables {
+      factor_id: nvafg.getId(&factor.label).to_string(),
+      fnctype: Some(factor.fnctype.type_str()),
+      data: factor.data.as_ref().map(|fd| fd.to_json()),
+      tags: Some(factor.tags.clone()),
+      solvable: factor.solvable,
+      timestamp: factor.timestamp.map(|ts| ts.to_string()),
+      nstime: factor.nstime.clone(),
+      metadata: factor.metadata.clone(),
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    update_factor::Variables,
+    update_factor::ResponseData,
+    Uuid
+  >(
+    &nvafg.client,
+    request_body, 
+    |s| {
+      return Uuid::parse_str(&s.update_factors.factors[0].id).expect("post_update_factor not able to parse uuid from API response");
+    },
+    Some(1)
+  ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn getFactor(
+  nvafg: &NavAbilityDFG,
+  label: &str,
+) -> Result<Option<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+  return crate::execute(post_get_factor(nvafg, label));
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn listFactors(
+  nvafg: &NavAbilityDFG,
+  filter: &ListFactorsFilter,
+) -> Result<Vec<String>, Box<dyn Error>> {
+  return crate::execute(post_list_factors(nvafg, filter));
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn deleteFactor(
+  nvafg: &NavAbilityDFG,
+  label: &str,
+) -> Result<delete_factor::ResponseData, Box<dyn Error>> {
+  return crate::execute(post_delete_factor(nvafg, label));
+}
@@ -0,0 +1,437 @@
+
+use std::{
+  collections::BTreeSet,
+  error::Error,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+  fs::File,
+  io::{Read, Write},
+  path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  Uuid,
+  Utc,
+  FactorGraphStore,
+  LocalDFG,
+  VariableDFG,
+  FactorDFG,
+  FactorType,
+  FunctionData,
+  AnyFactor,
+  ExportOptions,
+  ImportOptions,
+  ImportReport,
+  ImportError,
+  ValidationError,
+  SDK_VERSION,
+};
+
+
+/// Factor as written by DistributedFactorGraphs, i.e. `PackedFactor` with the function data
+/// serialized into the `data` string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+struct PackedFactorDFG {
+  id: Option<Uuid>,
+  label: String,
+  tags: Vec<String>,
+  _variableOrderSymbols: Vec<String>,
+  timestamp: chrono::DateTime<Utc>,
+  nstime: String,
+  fnctype: String,
+  solvable: i64,
+  data: String,
+  metadata: String,
+  _version: String,
+}
+
+
+impl<F: FactorType> FactorDFG<F> {
+  /// JSON of the factor in the DistributedFactorGraphs file layout.
+  pub fn to_dfg_json(&self) -> Result<String, Box<dyn Error>> {
+    let packed = PackedFactorDFG {
+      id: self.id,
+      label: self.label.clone(),
+      tags: self.tags.clone(),
+      _variableOrderSymbols: self.variableOrderSymbols_.clone(),
+      timestamp: self.timestamp.unwrap_or(Utc::now()),
+      nstime: self.nstime.clone().unwrap_or("0".to_owned()),
+      fnctype: self.fnctype.type_str(),
+      solvable: self.solvable.unwrap_or(1),
+      data: self.data.as_ref().map(|d| d.to_json()).unwrap_or_default(),
+      metadata: crate::utils::NodeMetadata::base64_or_empty(self.metadata.as_deref().unwrap_or("")),
+      _version: self._version.clone().unwrap_or(SDK_VERSION.to_owned()),
+    };
+    return Ok(serde_json::to_string(&packed)?);
+  }
+}
+
+
+impl FactorDFG<AnyFactor> {
+  /// Read a factor from JSON in the DistributedFactorGraphs file layout, factor types not supported
+  /// by the SDK are kept as `AnyFactor::Unknown`.
+  pub fn from_dfg_json(
+    json: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    let packed: PackedFactorDFG = serde_json::from_str(json)?;
+    let data = FunctionData::from_json(&packed.data)?;
+    let fnc = match &data.fnc {
+      serde_json::Value::String(s) => s.clone(),
+      v => v.to_string(),
+    };
+    return Ok(Self {
+      id: packed.id,
+      label: packed.label,
+      tags: packed.tags,
+      variableOrderSymbols_: packed._variableOrderSymbols,
+      timestamp: Some(packed.timestamp),
+      nstime: Some(packed.nstime),
+      fnctype: AnyFactor::from_parts(&packed.fnctype, &fnc),
+      solvable: Some(packed.solvable),
+      data: Some(data),
+      metadata: Some(packed.metadata),
+      _version: Some(packed._version),
+      lastUpdatedTimestamp: None,
+    });
+  }
+}
+
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+
+/// Add one regular file to the archive.
+#[cfg(not(target_arch = "wasm32"))]
+fn append_file<W: Write>(
+  archive: &mut tar::Builder<W>,
+  path: &str,
+  contents: &[u8],
+) -> Result<(), Box<dyn Error>> {
+  let mut header = tar::Header::new_gnu();
+  header.set_size(contents.len() as u64);
+  header.set_mode(0o644);
+  header.set_mtime(Utc::now().timestamp() as u64);
+  header.set_entry_type(tar::EntryType::Regular);
+  archive.append_data(&mut header, path, contents)?;
+  return Ok(());
+}
+
+
+/// Write a factor graph to a `.tar.gz` archive in the DistributedFactorGraphs layout, which can be
+/// loaded with `loadDFG` in Julia:
+/// ```text
+/// <name>/graphinfo.json
+/// <name>/variables/<label>.json
+/// <name>/factors/<label>.json
+/// ```
+/// where `<name>` is the file name without `.tar.gz`, which is appended to `path` if missing.
+/// The labels are listed first, then variables (with PPEs and solver data) and factors are read in
+/// pages of `ExportOptions::page_size` with `getVariables` and `getFactors`, and written to the
+/// archive as they arrive.  `graphinfo.json` holds the label and description of the graph.  The
+/// archive is first written next to `path` and only moved in place once complete, so a failed
+/// export does not leave a truncated file behind.
+/// Returns the path of the archive.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn export_graph<G: FactorGraphStore>(
+  dfg: &G,
+  path: &str,
+  options: &ExportOptions,
+) -> Result<String, Box<dyn Error>> {
+  let path = if path.ends_with(".tar.gz") {
+    path.to_owned()
+  } else {
+    format!("{}.tar.gz", path)
+  };
+  let name = Path::new(&path)
+    .file_name()
+    .and_then(|n| n.to_str())
+    .map(|n| n.trim_end_matches(".tar.gz").to_owned())
+    .unwrap_or_default();
+  if name.is_empty() {
+    return Err(Box::new(crate::ValidationError {
+      details: format!("export_graph requires a file name, got {}", &path),
+    }));
+  }
+  let page_size = if options.page_size == 0 { DEFAULT_PAGE_SIZE } else { options.page_size };
+
+  let partial = format!("{}.part", &path);
+  let encoder = flate2::write::GzEncoder::new(File::create(&partial)?, flate2::Compression::default());
+  let mut archive = tar::Builder::new(encoder);
+
+  let result = async {
+    let graphinfo = serde_json::json!({
+      "graphLabel": dfg.getGraphLabel(),
+      "description": dfg.getDescription().await?,
+      "_version": SDK_VERSION,
+    });
+    append_file(&mut archive, &format!("{}/graphinfo.json", &name), graphinfo.to_string().as_bytes())?;
+
+    // variables deleted since listing are left out of a page
+    for page in dfg.listVariables().await?.chunks(page_size) {
+      for mut v in dfg.getVariables(page).await? {
+        if !options.include_blob_entries {
+          v.blobEntries.clear();
+        }
+        v.metadata = crate::utils::NodeMetadata::base64_or_empty(&v.metadata);
+        let json = serde_json::to_string(&v)?;
+        append_file(&mut archive, &format!("{}/variables/{}.json", &name, &v.label), json.as_bytes())?;
+      }
+    }
+
+    for page in dfg.lsf().await?.chunks(page_size) {
+      for f in dfg.getFactors(page).await? {
+        let json = f.to_dfg_json()?;
+        append_file(&mut archive, &format!("{}/factors/{}.json", &name, &f.label), json.as_bytes())?;
+      }
+    }
+
+    archive.into_inner()?.finish()?.sync_all()?;
+    return Ok::<(), Box<dyn Error>>(());
+  }.await;
+
+  if let Err(e) = result {
+    let _ = std::fs::remove_file(&partial);
+    return Err(e);
+  }
+  std::fs::rename(&partial, &path)?;
+  return Ok(path);
+}
+
+
+const DEFAULT_IMPORT_BATCH: usize = 100;
+
+
+/// Labels as accepted by DistributedFactorGraphs, i.e. `^[a-zA-Z][a-zA-Z0-9_]*$`.
+fn is_valid_label(
+  label: &str,
+) -> bool {
+  let mut chars = label.chars();
+  return match chars.next() {
+    Some(c) if c.is_ascii_alphabetic() => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+    _ => false,
+  };
+}
+
+
+/// Read all variables and factors from a DistributedFactorGraphs archive, see `export_graph` for
+/// the layout.  Nodes keep the ids from the archive.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_archive(
+  path: &str,
+) -> Result<LocalDFG, Box<dyn Error>> {
+  let mut dfg = LocalDFG::default();
+  dfg.label = Path::new(path)
+    .file_name()
+    .and_then(|n| n.to_str())
+    .map(|n| n.trim_end_matches(".tar.gz").to_owned())
+    .unwrap_or_default();
+  let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(File::open(path)?));
+  for entry in archive.entries()? {
+    let mut entry = entry?;
+    let entrypath = entry.path()?.into_owned();
+    let folder = entrypath.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()).unwrap_or("").to_owned();
+    if !entry.header().entry_type().is_file() || entrypath.extension().map_or(true, |e| e != "json") {
+      continue;
+    }
+    let mut json = String::new();
+    entry.read_to_string(&mut json)?;
+    let invalid = |e: &dyn std::fmt::Display| -> Box<dyn Error> {
+      return Box::new(ValidationError {
+        details: format!("unable to read {} from {}: {}", entrypath.display(), path, e),
+      });
+    };
+    match folder.as_str() {
+      "variables" => {
+        let v: VariableDFG = serde_json::from_str(&json).map_err(|e| invalid(&e))?;
+        dfg.variables.insert(v.label.clone(), v);
+      },
+      "factors" => {
+        let f = FactorDFG::from_dfg_json(&json).map_err(|e| invalid(&e))?;
+        dfg.factors.insert(f.label.clone(), f);
+      },
+      _ => {},
+    }
+  }
+  return Ok(dfg);
+}
+
+
+/// Problems that prevent importing a graph, e.g. invalid labels or factors connected to missing
+/// variables.
+fn validate_import(
+  source: &LocalDFG,
+) -> Vec<String> {
+  let mut problems = Vec::new();
+  for (label, v) in &source.variables {
+    if !is_valid_label(label) {
+      problems.push(format!("invalid variable label {:?}", label));
+    }
+    for ppe in &v.ppes {
+      if ppe.solveKey.is_empty() {
+        problems.push(format!("variable {} has a PPE without solveKey", label));
+      }
+    }
+  }
+  for (label, f) in &source.factors {
+    if !is_valid_label(label) {
+      problems.push(format!("invalid factor label {:?}", label));
+    }
+    for vl in &f.variableOrderSymbols_ {
+      if !source.variables.contains_key(vl) {
+        problems.push(format!("factor {} connects variable {} which is not imported", label, vl));
+      }
+    }
+    if let Err(e) = f.validate() {
+      problems.push(e.to_string());
+    }
+  }
+  return problems;
+}
+
+
+fn import_failed(
+  report: &ImportReport,
+  e: Box<dyn Error>,
+) -> Box<dyn Error> {
+  return Box::new(ImportError {
+    details: format!(
+      "after adding {} variables and {} factors: {}, rerun with `ImportOptions::resume` to continue",
+      report.variables.len(), report.factors.len(), e
+    ),
+  });
+}
+
+
+/// Read a DistributedFactorGraphs `.tar.gz` archive, e.g. from `export_graph` or `saveDFG` in Julia,
+/// and add its variables, PPEs, blob entries (optional) and factors to a new or existing graph,
+/// see `import_local_dfg`.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn import_graph<G: FactorGraphStore>(
+  dfg: &mut G,
+  path: &str,
+  options: &ImportOptions,
+) -> Result<ImportReport, Box<dyn Error>> {
+  return import_local_dfg(dfg, &read_archive(path)?, options).await;
+}
+
+
+/// Add the variables, PPEs, blob entries (optional) and factors of a local graph to a new or
+/// existing graph, e.g. to upload a graph read with `read_archive` or `read_pose_graph`.
+/// The whole source is validated first, and nothing is written if there are problems, which a dry
+/// run returns in `ImportReport::problems` instead.  Factor types not supported by the SDK are
+/// imported unchanged, a dry run lists them in `ImportReport::warnings`.  Variables are added
+/// before factors, both in batches of `ImportOptions::batch_size`, and a failed import can be
+/// continued with `ImportOptions::resume`.  Ids are assigned by the target graph and solver data
+/// is not imported, i.e. the graph needs to be solved again, see
+/// `ImportReport::solver_data_skipped`.
+pub async fn import_local_dfg<G: FactorGraphStore>(
+  dfg: &mut G,
+  source: &LocalDFG,
+  options: &ImportOptions,
+) -> Result<ImportReport, Box<dyn Error>> {
+  let mut report = ImportReport::default();
+  report.problems = validate_import(source);
+  if !report.problems.is_empty() && !options.dry_run {
+    return Err(Box::new(ValidationError {
+      details: format!("graph {} can not be imported: {}", &source.label, report.problems.join("; ")),
+    }));
+  }
+
+  let (existing_variables, existing_factors): (BTreeSet<String>, BTreeSet<String>) = if options.resume {
+    (dfg.listVariables().await?.into_iter().collect(), dfg.lsf().await?.into_iter().collect())
+  } else {
+    (BTreeSet::new(), BTreeSet::new())
+  };
+
+  if options.dry_run {
+    for (label, f) in &source.factors {
+      if let AnyFactor::Unknown { fnctype, .. } = &f.fnctype {
+        report.warnings.push(format!("factor {} has type {} which is not supported by the SDK, see `register_factor_type`", label, fnctype));
+      }
+    }
+    for label in source.variables.keys().chain(source.factors.keys()) {
+      if existing_variables.contains(label) || existing_factors.contains(label) {
+        report.skipped.push(label.clone());
+      } else if let Some(v) = source.variables.get(label) {
+        report.variables.push(label.clone());
+        report.ppes += v.ppes.len();
+        report.solver_data_skipped += v.solverData.len();
+        if options.include_blob_entries {
+          report.blob_entries += v.blobEntries.len();
+        }
+      } else {
+        report.factors.push(label.clone());
+      }
+    }
+    return Ok(report);
+  }
+
+  let batch_size = if options.batch_size == 0 { DEFAULT_IMPORT_BATCH } else { options.batch_size };
+  let variables: Vec<&VariableDFG> = source.variables.values().collect();
+  for batch in variables.chunks(batch_size) {
+    let mut nodes = Vec::new();
+    for v in batch {
+      report.solver_data_skipped += v.solverData.len();
+      if existing_variables.contains(&v.label) {
+        report.skipped.push(v.label.clone());
+      } else {
+        let mut node = (*v).clone();
+        node.id = None;
+        nodes.push(node);
+      }
+    }
+    if !nodes.is_empty() {
+      dfg.addVariables(&nodes).await.map_err(|e| import_failed(&report, e))?;
+      report.variables.extend(nodes.iter().map(|n| n.label.clone()));
+    }
+
+    for v in batch {
+      let exists = existing_variables.contains(&v.label);
+      for ppe in &v.ppes {
+        let present = exists && dfg.getPPE(&v.label, &ppe.solveKey).await.map_err(|e| import_failed(&report, e))?.is_some();
+        if !present {
+          let mut ppe = ppe.clone();
+          ppe.id = None;
+          dfg.addPPE(&v.label, &ppe).await.map_err(|e| import_failed(&report, e))?;
+          report.ppes += 1;
+        }
+      }
+      if !options.include_blob_entries {
+        continue;
+      }
+      for entry in &v.blobEntries {
+        let present = exists && dfg.getBlobEntry(&v.label, &entry.label).await.map_err(|e| import_failed(&report, e))?.is_some();
+        if !present {
+          let mut entry = entry.clone();
+          entry.id = None;
+          dfg.addBlobEntry(&v.label, &entry).await.map_err(|e| import_failed(&report, e))?;
+          report.blob_entries += 1;
+        }
+      }
+    }
+  }
+
+  let mut pending = Vec::new();
+  for (label, f) in &source.factors {
+    if existing_factors.contains(label) {
+      report.skipped.push(label.clone());
+    } else {
+      let mut f = f.clone();
+      f.id = None;
+      pending.push(f);
+    }
+  }
+  while !pending.is_empty() {
+    let batch: Vec<FactorDFG<AnyFactor>> = pending.drain(..batch_size.min(pending.len())).collect();
+    let labels: Vec<String> = batch.iter().map(|f| f.label.clone()).collect();
+    dfg.addFactors(batch).await.map_err(|e| import_failed(&report, e))?;
+    report.factors.extend(labels);
+  }
+
+  return Ok(report);
+}
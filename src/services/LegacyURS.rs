@@ -7,8 +7,6 @@ use crate::{
     Response,
     Error,
     SDK_VERSION,
-    GetURS,
-    get_urs,
     to_console_debug,
     to_console_error,
 };
@@ -16,6 +14,8 @@ use crate::{
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::{
+    GetURS,
+    get_urs,
     NavAbilityClient,
     check_deser,
 };
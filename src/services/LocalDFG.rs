@@ -0,0 +1,477 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+};
+
+use crate::{
+    Uuid,
+    Utc,
+    GetId,
+    LocalDFG,
+    FactorGraphStore,
+    VariableDFG,
+    VariableType,
+    FactorDFG,
+    FactorType,
+    AnyFactor,
+    ListFactorsFilter,
+    MeanMaxPPE,
+    BlobEntry,
+    ConflictError,
+    NotFoundError,
+    Adjacency,
+};
+
+
+impl GetId for LocalDFG {
+    fn getId(
+        &self,
+        label: &str
+    ) -> Uuid {
+        return Uuid::new_v5(
+            &self.namespace,
+            (self.label.clone() + label).as_bytes()
+        )
+    }
+}
+
+
+fn not_found(
+    details: String
+) -> Box<dyn Error> {
+    return Box::new(NotFoundError { details });
+}
+
+fn conflict(
+    details: String
+) -> Box<dyn Error> {
+    return Box::new(ConflictError { details });
+}
+
+
+impl LocalDFG {
+    /// Create an empty graph, use the same `namespace` (user id) and `label` as a remote
+    /// `NavAbilityDFG` to get the same node ids.
+    pub fn new(
+        namespace: Uuid,
+        label: &str,
+    ) -> Self {
+        return Self {
+            namespace,
+            label: label.to_owned(),
+            variables: BTreeMap::new(),
+            factors: BTreeMap::new(),
+        };
+    }
+
+    // ===================== Variables =========================
+
+    /// Add a variable, same arguments and defaults as `post_add_variable`.
+    pub fn add_variable<V: VariableType>(
+        &mut self,
+        label: &str,
+        variableType: &V,
+        tags: Option<Vec<String>>,
+        solvable: Option<i64>,
+        timestamp: Option<chrono::DateTime<Utc>>,
+        nstime: Option<usize>,
+        metadata: Option<String>,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        let mut variable = VariableDFG::new_typestr(
+            label,
+            &variableType.type_str(),
+            Some(timestamp.unwrap_or(Utc::now())),
+            nstime
+        );
+        variable.tags = tags.unwrap_or(vec!["VARIABLE".to_owned()]);
+        variable.solvable = solvable.unwrap_or(1) as i32;
+        variable.metadata = crate::utils::NodeMetadata::base64_or_empty(&metadata.unwrap_or_default());
+        return self.add_variable_dfg(variable);
+    }
+
+    /// Add an already assembled variable including its PPEs, solver data and blob entries, errors if a
+    /// variable with the same label exists.  `FactorGraphStore::addVariable` adds the variable node only.
+    pub fn add_variable_dfg(
+        &mut self,
+        mut variable: VariableDFG,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        if self.variables.contains_key(&variable.label) {
+            return Err(conflict(format!("variable {} already exists in graph {}", &variable.label, &self.label)));
+        }
+        let id = *variable.id.get_or_insert(self.getId(&variable.label));
+        variable.lastUpdatedTimestamp = Some(Utc::now());
+        self.variables.insert(variable.label.clone(), variable);
+        return Ok(id);
+    }
+
+    pub fn get_variable(
+        &self,
+        label: &str,
+    ) -> Option<&VariableDFG> {
+        return self.variables.get(label);
+    }
+
+    /// Replace an existing variable with the same label, including its PPEs, solver data and blob entries.
+    pub fn update_variable(
+        &mut self,
+        mut variable: VariableDFG,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        if !self.variables.contains_key(&variable.label) {
+            return Err(not_found(format!("variable {} in graph {}", &variable.label, &self.label)));
+        }
+        let id = *variable.id.get_or_insert(self.getId(&variable.label));
+        variable.lastUpdatedTimestamp = Some(Utc::now());
+        self.variables.insert(variable.label.clone(), variable);
+        return Ok(id);
+    }
+
+    pub fn list_variables(
+        &self,
+    ) -> Vec<String> {
+        return self.variables.keys().cloned().collect();
+    }
+
+    /// Delete a variable together with all factors connected to it, as in DistributedFactorGraphs.jl.
+    pub fn delete_variable(
+        &mut self,
+        label: &str,
+    ) -> Result<(VariableDFG, Vec<FactorDFG<AnyFactor>>), Box<dyn Error>> {
+        let variable = match self.variables.remove(label) {
+            Some(v) => v,
+            None => return Err(not_found(format!("variable {} in graph {}", label, &self.label))),
+        };
+        let flbls: Vec<String> = self.factors.values()
+            .filter(|f| f.variableOrderSymbols_.iter().any(|v| v == label))
+            .map(|f| f.label.clone())
+            .collect();
+        let mut factors = Vec::new();
+        for fl in flbls {
+            if let Some(f) = self.factors.remove(&fl) {
+                factors.push(f);
+            }
+        }
+        return Ok((variable, factors));
+    }
+
+    // ===================== Factors =========================
+
+    /// Add a factor, behaves like `post_add_factor`: adding the same factor again returns its id,
+    /// while a different factor with the same label is a `ConflictError`.  All connected variables
+    /// must already exist.
+    pub fn add_factor<F: FactorType>(
+        &mut self,
+        factor: FactorDFG<F>,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        factor.validate()?;
+
+        if let Some(existing) = self.factors.get(&factor.label) {
+            if factor.is_same_factor(existing) {
+                return Ok(existing.id.unwrap_or(self.getId(&factor.label)));
+            }
+            return Err(conflict(format!("factor {} already exists with a different type, variables or data", &factor.label)));
+        }
+        for vl in &factor.variableOrderSymbols_ {
+            if !self.variables.contains_key(vl) {
+                return Err(not_found(format!("variable {} connected to factor {}", vl, &factor.label)));
+            }
+        }
+
+        let mut f = factor.to_any()?;
+        let id = *f.id.get_or_insert(self.getId(&f.label));
+        f.lastUpdatedTimestamp = Some(Utc::now());
+        self.factors.insert(f.label.clone(), f);
+        return Ok(id);
+    }
+
+    /// Replace an existing factor with the same label, the connected variables cannot be changed.
+    pub fn update_factor<F: FactorType>(
+        &mut self,
+        factor: &FactorDFG<F>,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        factor.validate()?;
+
+        let existing = match self.factors.get(&factor.label) {
+            Some(f) => f,
+            None => return Err(not_found(format!("factor {} in graph {}", &factor.label, &self.label))),
+        };
+        if existing.variableOrderSymbols_ != factor.variableOrderSymbols_ {
+            return Err(conflict(format!("factor {} cannot change its variables", &factor.label)));
+        }
+
+        let mut f = factor.to_any()?;
+        let id = *f.id.get_or_insert(self.getId(&f.label));
+        f.lastUpdatedTimestamp = Some(Utc::now());
+        self.factors.insert(f.label.clone(), f);
+        return Ok(id);
+    }
+
+    pub fn get_factor(
+        &self,
+        label: &str,
+    ) -> Option<&FactorDFG<AnyFactor>> {
+        return self.factors.get(label);
+    }
+
+    /// List factor labels, with the same filter semantics as `post_list_factors`.
+    pub fn list_factors(
+        &self,
+        filter: &ListFactorsFilter,
+    ) -> Vec<String> {
+        return self.factors.values()
+            .filter(|f| filter.tag.as_ref().map_or(true, |t| f.tags.contains(t)))
+            .filter(|f| filter.solvable.map_or(true, |s| s <= f.solvable.unwrap_or(0)))
+            .filter(|f| filter.variable.as_ref().map_or(true, |v| f.variableOrderSymbols_.contains(v)))
+            .filter(|f| filter.fnctype.as_ref().map_or(true, |t| {
+                let ftype = f.fnctype.type_str();
+                ftype == *t || ftype.rsplit('.').next() == t.rsplit('.').next()
+            }))
+            .map(|f| f.label.clone())
+            .collect();
+    }
+
+    pub fn delete_factor(
+        &mut self,
+        label: &str,
+    ) -> Result<FactorDFG<AnyFactor>, Box<dyn Error>> {
+        return self.factors.remove(label)
+            .ok_or_else(|| not_found(format!("factor {} in graph {}", label, &self.label)));
+    }
+
+    /// Labels of the nodes connected to `label`, i.e. the factors of a variable or the variables
+    /// of a factor.
+    pub fn get_neighbors(
+        &self,
+        label: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        if let Some(f) = self.factors.get(label) {
+            return Ok(f.variableOrderSymbols_.clone());
+        }
+        if self.variables.contains_key(label) {
+            return Ok(self.list_factors(&ListFactorsFilter {
+                variable: Some(label.to_owned()),
+                ..Default::default()
+            }));
+        }
+        return Err(not_found(format!("node {} in graph {}", label, &self.label)));
+    }
+
+    // ===================== PPEs =========================
+
+    fn variable_mut(
+        &mut self,
+        label: &str,
+    ) -> Result<&mut VariableDFG, Box<dyn Error>> {
+        let fglb = self.label.clone();
+        return self.variables.get_mut(label)
+            .ok_or_else(|| not_found(format!("variable {} in graph {}", label, fglb)));
+    }
+
+    /// Add a PPE to a variable, errors if the variable already has a PPE with the same `solveKey`.
+    pub fn add_ppe(
+        &mut self,
+        variable: &str,
+        mut ppe: MeanMaxPPE,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        let id = *ppe.id.get_or_insert(self.getId(&format!("{}{}", variable, ppe.solveKey)));
+        ppe.lastUpdatedTimestamp = Some(Utc::now());
+        let v = self.variable_mut(variable)?;
+        if v.ppes.iter().any(|p| p.solveKey == ppe.solveKey) {
+            return Err(conflict(format!("ppe {} already exists on variable {}", &ppe.solveKey, variable)));
+        }
+        v.ppes.push(ppe);
+        return Ok(id);
+    }
+
+    pub fn get_ppe(
+        &self,
+        variable: &str,
+        solveKey: &str,
+    ) -> Option<&MeanMaxPPE> {
+        return self.variables.get(variable)?.ppes.iter().find(|p| p.solveKey == solveKey);
+    }
+
+    /// Replace the PPE with the same `solveKey`.
+    pub fn update_ppe(
+        &mut self,
+        variable: &str,
+        mut ppe: MeanMaxPPE,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        let id = *ppe.id.get_or_insert(self.getId(&format!("{}{}", variable, ppe.solveKey)));
+        ppe.lastUpdatedTimestamp = Some(Utc::now());
+        let v = self.variable_mut(variable)?;
+        match v.ppes.iter_mut().find(|p| p.solveKey == ppe.solveKey) {
+            Some(p) => *p = ppe,
+            None => return Err(not_found(format!("ppe {} on variable {}", &ppe.solveKey, variable))),
+        }
+        return Ok(id);
+    }
+
+    /// Solve keys of the PPEs on a variable.
+    pub fn list_ppes(
+        &self,
+        variable: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.variables.get(variable)
+            .map(|v| v.ppes.iter().map(|p| p.solveKey.clone()).collect())
+            .ok_or_else(|| not_found(format!("variable {} in graph {}", variable, &self.label)));
+    }
+
+    pub fn delete_ppe(
+        &mut self,
+        variable: &str,
+        solveKey: &str,
+    ) -> Result<MeanMaxPPE, Box<dyn Error>> {
+        let v = self.variable_mut(variable)?;
+        return match v.ppes.iter().position(|p| p.solveKey == solveKey) {
+            Some(i) => Ok(v.ppes.remove(i)),
+            None => Err(not_found(format!("ppe {} on variable {}", solveKey, variable))),
+        };
+    }
+
+    // ===================== Blob Entries =========================
+
+    /// Add a blob entry to a variable, errors if the variable already has an entry with the same label.
+    pub fn add_blob_entry(
+        &mut self,
+        variable: &str,
+        mut entry: BlobEntry,
+    ) -> Result<Uuid, Box<dyn Error>> {
+        let id = *entry.id.get_or_insert(self.getId(&format!("{}{}", variable, entry.label)));
+        entry.lastUpdatedTimestamp = Some(Utc::now());
+        let v = self.variable_mut(variable)?;
+        if v.blobEntries.iter().any(|be| be.label == entry.label) {
+            return Err(conflict(format!("blob entry {} already exists on variable {}", &entry.label, variable)));
+        }
+        v.blobEntries.push(entry);
+        return Ok(id);
+    }
+
+    pub fn get_blob_entry(
+        &self,
+        variable: &str,
+        label: &str,
+    ) -> Option<&BlobEntry> {
+        return self.variables.get(variable)?.blobEntries.iter().find(|be| be.label == label);
+    }
+
+    /// Labels of the blob entries on a variable.
+    pub fn list_blob_entries(
+        &self,
+        variable: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.variables.get(variable)
+            .map(|v| v.blobEntries.iter().map(|be| be.label.clone()).collect())
+            .ok_or_else(|| not_found(format!("variable {} in graph {}", variable, &self.label)));
+    }
+
+    pub fn delete_blob_entry(
+        &mut self,
+        variable: &str,
+        label: &str,
+    ) -> Result<BlobEntry, Box<dyn Error>> {
+        let v = self.variable_mut(variable)?;
+        return match v.blobEntries.iter().position(|be| be.label == label) {
+            Some(i) => Ok(v.blobEntries.remove(i)),
+            None => Err(not_found(format!("blob entry {} on variable {}", label, variable))),
+        };
+    }
+}
+
+
+#[allow(non_snake_case)]
+impl FactorGraphStore for LocalDFG {
+    fn getGraphLabel(&self) -> String {
+        return self.label.clone();
+    }
+
+    async fn getVariable(&self, label: &str) -> Result<Option<VariableDFG>, Box<dyn Error>> {
+        return Ok(self.get_variable(label).cloned());
+    }
+
+    async fn addVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>> {
+        // same as NavAbilityDFG, child nodes are added separately
+        let mut variable = variable.clone();
+        variable.ppes.clear();
+        variable.solverData.clear();
+        variable.blobEntries.clear();
+        return self.add_variable_dfg(variable);
+    }
+
+    async fn updateVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>> {
+        let mut variable = variable.clone();
+        if let Some(existing) = self.variables.get(&variable.label) {
+            variable.ppes = existing.ppes.clone();
+            variable.solverData = existing.solverData.clone();
+            variable.blobEntries = existing.blobEntries.clone();
+        }
+        return self.update_variable(variable);
+    }
+
+    async fn deleteVariable(&mut self, label: &str) -> Result<(), Box<dyn Error>> {
+        return self.delete_variable(label).map(|_| ());
+    }
+
+    async fn listVariables(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        return Ok(self.list_variables());
+    }
+
+    async fn getFactor(&self, label: &str) -> Result<Option<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+        return Ok(self.get_factor(label).cloned());
+    }
+
+    async fn addFactor<F: FactorType>(&mut self, factor: FactorDFG<F>) -> Result<Uuid, Box<dyn Error>> {
+        return self.add_factor(factor);
+    }
+
+    async fn updateFactor<F: FactorType>(&mut self, factor: &FactorDFG<F>) -> Result<Uuid, Box<dyn Error>> {
+        return self.update_factor(factor);
+    }
+
+    async fn deleteFactor(&mut self, label: &str) -> Result<(), Box<dyn Error>> {
+        return self.delete_factor(label).map(|_| ());
+    }
+
+    async fn listFactors(&self, filter: &ListFactorsFilter) -> Result<Vec<String>, Box<dyn Error>> {
+        return Ok(self.list_factors(filter));
+    }
+
+    async fn getNeighbors(&self, label: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.get_neighbors(label);
+    }
+
+    async fn getAdjacency(&self) -> Result<Adjacency, Box<dyn Error>> {
+        return Ok(Adjacency::from_local(self));
+    }
+
+    async fn getBlobEntry(&self, variableLabel: &str, label: &str) -> Result<Option<BlobEntry>, Box<dyn Error>> {
+        return Ok(self.get_blob_entry(variableLabel, label).cloned());
+    }
+
+    async fn addBlobEntry(&mut self, variableLabel: &str, entry: &BlobEntry) -> Result<Uuid, Box<dyn Error>> {
+        return self.add_blob_entry(variableLabel, entry.clone());
+    }
+
+    async fn deleteBlobEntry(&mut self, variableLabel: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        return self.delete_blob_entry(variableLabel, label).map(|_| ());
+    }
+
+    async fn getPPE(&self, variableLabel: &str, solveKey: &str) -> Result<Option<MeanMaxPPE>, Box<dyn Error>> {
+        return Ok(self.get_ppe(variableLabel, solveKey).cloned());
+    }
+
+    async fn addPPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>> {
+        return self.add_ppe(variableLabel, ppe.clone());
+    }
+
+    async fn updatePPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>> {
+        return self.update_ppe(variableLabel, ppe.clone());
+    }
+
+    async fn deletePPE(&mut self, variableLabel: &str, solveKey: &str) -> Result<(), Box<dyn Error>> {
+        return self.delete_ppe(variableLabel, solveKey).map(|_| ());
+    }
+
+    async fn listPPEs(&self, variableLabel: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        return self.list_ppes(variableLabel);
+    }
+}
@@ -24,8 +24,78 @@ use crate::{
     to_console_debug,
     to_console_error,
     post_to_nvaapi,
+    parse_str_utc,
+    Model,
+    NotFoundError,
+    GetModel,
+    get_model,
+    ListModelBlobEntries,
+    list_model_blob_entries,
+    UpdateModel,
+    update_model,
+    DeleteModel,
+    delete_model,
+    ConnectModelAgent,
+    connect_model_agent,
+    ConnectModelGraph,
+    connect_model_graph,
 };
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use std::collections::HashMap;
+
+
+// ===================== HELPERS ========================
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+impl Model {
+    /// Errors if the id or a timestamp of the model or of a connected agent or graph can not be
+    /// parsed, instead of leaving it out or using the epoch.
+    pub fn from_gql(
+        mgql: &get_model::GetModelModels,
+    ) -> Result<Self, Box<dyn Error>> {
+        let summary = &mgql.model_fields_summary;
+        let full = &mgql.model_fields_full;
+
+        let mut model = Model::default();
+        model.label = summary.label.clone();
+        model.id = Some(Uuid::parse_str(&summary.id)
+            .map_err(|e| format!("id of model {}: {}", &model.label, e))?);
+        model.description = summary.description.clone().unwrap_or_default();
+        model.tags = summary.tags.clone();
+        model._version = summary.version.clone();
+        model.createdTimestamp = parse_str_utc(summary.created_timestamp.clone())
+            .map_err(|e| format!("createdTimestamp of model {}: {}", &model.label, e))?;
+        model.lastUpdatedTimestamp = Some(parse_str_utc(summary.last_updated_timestamp.clone())
+            .map_err(|e| format!("lastUpdatedTimestamp of model {}: {}", &model.label, e))?);
+
+        model.metadata = full.metadata.clone();
+        let mut blob_entries = HashMap::new();
+        for ge in &full.blob_entries {
+            let ne = BlobEntry::from_gql_summary(ge);
+            blob_entries.insert(ne.label.clone(), ne);
+        }
+        model.blobEntries = Some(blob_entries);
+        model.agents = Some(full.agents.iter().map(|a| {
+            parse_str_utc(a.last_updated_timestamp.clone())
+                .map(|ts| (a.label.clone(), ts))
+                .map_err(|e| format!("agent {} of model {}: {}", &a.label, &model.label, e))
+        }).collect::<Result<Vec<_>, String>>()?);
+        model.fgs = Some(full.fgs.iter().map(|g| {
+            parse_str_utc(g.last_updated_timestamp.clone())
+                .map(|ts| (g.label.clone(), ts))
+                .map_err(|e| format!("factorgraph {} of model {}: {}", &g.label, &model.label, e))
+        }).collect::<Result<Vec<_>, String>>()?);
+
+        return Ok(model);
+    }
+}
+
+
+// ===================== QUERIES ========================
+
+
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 pub fn list_models_query(
@@ -110,10 +180,7 @@ pub async fn add_entry_model_async(
     if let Some(sz) = entry.size {
         size_s = Some(format!("{}",sz));
     }
-    let mut metadata = entry.metadata.to_string();
-    if metadata.is_empty() {
-        metadata = "e30=".to_string();
-    }
+    let metadata = crate::utils::NodeMetadata::base64_or_empty(&entry.metadata);
 
     let variables = add_blob_entry_model::Variables {
         model_label: model_label.to_string(),
@@ -170,3 +237,279 @@ pub async fn post_list_model_graphs(
         Some(3)
     ).await;
 }
+
+
+
+/// Get a model with its metadata, blob entry summaries, and connected agents and graphs, `None`
+/// if the org has no model with this label.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_model(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<Option<Model>, Box<dyn Error>> {
+
+    let request_body = GetModel::build_query(get_model::Variables {
+        model_id: nvacl.getId(model_label).to_string(),
+        full: Some(true),
+    });
+
+    return post_to_nvaapi::<
+        get_model::Variables,
+        get_model::ResponseData,
+        Option<Result<Model, Box<dyn Error>>>
+    >(
+        nvacl,
+        request_body, 
+        |s| s.models.first().map(Model::from_gql),
+        Some(3)
+    ).await?.transpose();
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn get_model(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<Option<Model>, Box<dyn Error>> {
+    return crate::execute(post_get_model(nvacl, model_label));
+}
+
+
+
+/// Models with a label containing `model_label_contains`, or all models.  Only `id`, `label`,
+/// `tags` and `lastUpdatedTimestamp` are filled, the other fields keep their defaults, see
+/// `post_get_model` for the details of a model.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_list_models(
+    nvacl: &NavAbilityClient,
+    model_label_contains: Option<&str>,
+) -> Result<Vec<Model>, Box<dyn Error>> {
+    let data = fetch_list_models(nvacl, model_label_contains).await?;
+
+    let mut models = Vec::new();
+    for m in data.models {
+        let mut model = Model::default();
+        model.id = Some(Uuid::parse_str(&m.id)
+            .map_err(|e| format!("id of model {}: {}", &m.label, e))?);
+        model.lastUpdatedTimestamp = Some(parse_str_utc(m.last_updated_timestamp)
+            .map_err(|e| format!("lastUpdatedTimestamp of model {}: {}", &m.label, e))?);
+        model.label = m.label;
+        model.tags = m.tags;
+        models.push(model);
+    }
+    return Ok(models);
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn list_models(
+    nvacl: &NavAbilityClient,
+    model_label_contains: Option<&str>,
+) -> Result<Vec<Model>, Box<dyn Error>> {
+    return crate::execute(post_list_models(nvacl, model_label_contains));
+}
+
+
+
+/// Update the description, tags and metadata of an existing model, e.g. after `post_get_model`.
+/// Only fields that are set are sent, an empty description, empty tags or `None` metadata are left
+/// unchanged, so a model from `post_list_models` does not clear what it was not listed with.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_update_model(
+    nvacl: &NavAbilityClient,
+    model: &Model,
+) -> Result<Uuid, Box<dyn Error>> {
+
+    let query = UpdateModel::build_query(update_model::Variables {
+        id: nvacl.getId(&model.label).to_string(),
+        description: Some(model.description.clone()).filter(|d| !d.is_empty()),
+        tags: Some(model.tags.clone()).filter(|t| !t.is_empty()),
+        metadata: model.metadata.as_deref().map(crate::utils::NodeMetadata::base64_or_empty),
+    });
+    // leave unset variables out, a null variable would set the field to null
+    let mut variables = serde_json::to_value(query.variables)?;
+    if let Some(vars) = variables.as_object_mut() {
+        vars.retain(|_, v| !v.is_null());
+    }
+    let request_body = QueryBody {
+        variables,
+        query: query.query,
+        operation_name: query.operation_name,
+    };
+
+    let ids = post_to_nvaapi::<
+        serde_json::Value,
+        update_model::ResponseData,
+        Vec<String>
+    >(
+        nvacl,
+        request_body, 
+        |s| s.update_models.models.iter().map(|m| m.id.clone()).collect(),
+        Some(1)
+    ).await?;
+
+    return match ids.first() {
+        Some(id) => Ok(Uuid::parse_str(id)?),
+        None => Err(Box::new(NotFoundError { details: format!("model {}", &model.label) })),
+    };
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn update_model(
+    nvacl: &NavAbilityClient,
+    model: &Model,
+) -> Result<Uuid, Box<dyn Error>> {
+    return crate::execute(post_update_model(nvacl, model));
+}
+
+
+
+/// Delete a model and its blob entries, the blobs, and the agents and graphs connected to the
+/// model are kept.  Returns the number of nodes deleted.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_delete_model(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<i64, Box<dyn Error>> {
+
+    let request_body = DeleteModel::build_query(delete_model::Variables {
+        model_id: nvacl.getId(model_label).to_string(),
+    });
+
+    let deleted = post_to_nvaapi::<
+        delete_model::Variables,
+        delete_model::ResponseData,
+        i64
+    >(
+        nvacl,
+        request_body, 
+        |s| s.delete_models.nodes_deleted,
+        Some(1)
+    ).await?;
+
+    if deleted == 0 {
+        return Err(Box::new(NotFoundError { details: format!("model {}", model_label) }));
+    }
+    return Ok(deleted);
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn delete_model(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<i64, Box<dyn Error>> {
+    return crate::execute(post_delete_model(nvacl, model_label));
+}
+
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_connect_model_agent(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+    agent_label: &str,
+) -> Result<connect_model_agent::ResponseData, Box<dyn Error>> {
+
+    let request_body = ConnectModelAgent::build_query(connect_model_agent::Variables {
+        model_id: nvacl.getId(model_label).to_string(),
+        agent_id: nvacl.getId(agent_label).to_string(),
+    });
+
+    return post_to_nvaapi::<
+        connect_model_agent::Variables,
+        connect_model_agent::ResponseData,
+        connect_model_agent::ResponseData
+    >(
+        nvacl,
+        request_body, 
+        |s| s,
+        Some(3)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn connect_model_agent(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+    agent_label: &str,
+) -> Result<connect_model_agent::ResponseData, Box<dyn Error>> {
+    return crate::execute(post_connect_model_agent(nvacl, model_label, agent_label));
+}
+
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_connect_model_graph(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+    fg_label: &str,
+) -> Result<connect_model_graph::ResponseData, Box<dyn Error>> {
+
+    let request_body = ConnectModelGraph::build_query(connect_model_graph::Variables {
+        model_id: nvacl.getId(model_label).to_string(),
+        fg_id: nvacl.getId(fg_label).to_string(),
+    });
+
+    return post_to_nvaapi::<
+        connect_model_graph::Variables,
+        connect_model_graph::ResponseData,
+        connect_model_graph::ResponseData
+    >(
+        nvacl,
+        request_body, 
+        |s| s,
+        Some(3)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn connect_model_graph(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+    fg_label: &str,
+) -> Result<connect_model_graph::ResponseData, Box<dyn Error>> {
+    return crate::execute(post_connect_model_graph(nvacl, model_label, fg_label));
+}
+
+
+
+/// Blob entries of a model with all their fields, e.g. calibration or URDF files of a robot model.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_list_model_entries(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<Vec<BlobEntry>, Box<dyn Error>> {
+
+    let request_body = ListModelBlobEntries::build_query(list_model_blob_entries::Variables {
+        model_id: nvacl.getId(model_label).to_string(),
+    });
+
+    let entries = post_to_nvaapi::<
+        list_model_blob_entries::Variables,
+        list_model_blob_entries::ResponseData,
+        Option<Vec<BlobEntry>>
+    >(
+        nvacl,
+        request_body, 
+        |s| s.models.first().map(|m| m.blob_entries.iter().map(BlobEntry::from_gql).collect()),
+        Some(3)
+    ).await?;
+
+    return match entries {
+        Some(entries) => Ok(entries),
+        None => Err(Box::new(NotFoundError { details: format!("model {}", model_label) })),
+    };
+}
+
+
+#[cfg(any(feature = "tokio", feature = "thread"))]
+pub fn list_model_entries(
+    nvacl: &NavAbilityClient,
+    model_label: &str,
+) -> Result<Vec<BlobEntry>, Box<dyn Error>> {
+    return crate::execute(post_list_model_entries(nvacl, model_label));
+}
@@ -9,11 +9,25 @@ use crate::{
     Factorgraph,
 };
 
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use std::error::Error;
+
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::{
     NavAbilityClient,
     NavAbilityDFG,
     NavAbilityBlobStore,
+    FactorGraphStore,
+    VariableDFG,
+    FactorDFG,
+    FactorType,
+    AnyFactor,
+    ListFactorsFilter,
+    MeanMaxPPE,
+    BlobEntry,
+    NotFoundError,
+    Adjacency,
+    services,
 };
 
 
@@ -67,7 +81,7 @@ impl NavAbilityDFG {
                 client, 
                 fgLabel,
                 "",
-                "e30="
+                &crate::utils::NodeMetadata::new().to_base64()
             );
         }
 
@@ -90,4 +104,147 @@ impl NavAbilityDFG {
             blobStores,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+fn not_found(
+    details: String
+) -> Box<dyn Error> {
+    return Box::new(NotFoundError { details });
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+impl FactorGraphStore for NavAbilityDFG {
+    fn getGraphLabel(&self) -> String {
+        return self.fg.label.clone();
+    }
+
+    async fn getDescription(&self) -> Result<String, Box<dyn Error>> {
+        let fg = services::post_get_factorgraph(&self.client, &self.fg.label).await?;
+        return Ok(fg.map(|fg| fg.description).unwrap_or_default());
+    }
+
+    async fn getVariable(&self, label: &str) -> Result<Option<VariableDFG>, Box<dyn Error>> {
+        return services::post_get_variable(self, label, true).await;
+    }
+
+    async fn addVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_add_variable_dfg(self, variable).await;
+    }
+
+    async fn addVariables(&mut self, variables: &[VariableDFG]) -> Result<Vec<Uuid>, Box<dyn Error>> {
+        return services::post_add_variables(self, variables).await;
+    }
+
+    async fn updateVariable(&mut self, variable: &VariableDFG) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_update_variable(self, variable).await;
+    }
+
+    async fn deleteVariable(&mut self, label: &str) -> Result<(), Box<dyn Error>> {
+        // connected factors are deleted in the same request
+        let info = services::post_delete_variable(self, label).await?;
+        if info.delete_variables.nodes_deleted == 0 {
+            return Err(not_found(format!("variable {} in graph {}", label, &self.fg.label)));
+        }
+        return Ok(());
+    }
+
+    async fn listVariables(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        return services::post_list_variables(self).await;
+    }
+
+    async fn getVariables(&self, labels: &[String]) -> Result<Vec<VariableDFG>, Box<dyn Error>> {
+        return services::post_get_variables(self, labels, true).await;
+    }
+
+    async fn getFactor(&self, label: &str) -> Result<Option<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+        return services::post_get_factor(self, label).await;
+    }
+
+    async fn getFactors(&self, labels: &[String]) -> Result<Vec<FactorDFG<AnyFactor>>, Box<dyn Error>> {
+        return services::post_get_factors(self, labels).await;
+    }
+
+    async fn addFactor<F: FactorType>(&mut self, factor: FactorDFG<F>) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_add_factor(self, factor).await;
+    }
+
+    async fn addFactors<F: FactorType>(&mut self, factors: Vec<FactorDFG<F>>) -> Result<Vec<Uuid>, Box<dyn Error>> {
+        return services::post_add_factors(self, factors).await;
+    }
+
+    async fn updateFactor<F: FactorType>(&mut self, factor: &FactorDFG<F>) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_update_factor(self, factor).await;
+    }
+
+    async fn deleteFactor(&mut self, label: &str) -> Result<(), Box<dyn Error>> {
+        let info = services::post_delete_factor(self, label).await?;
+        if info.delete_factors.nodes_deleted == 0 {
+            return Err(not_found(format!("factor {} in graph {}", label, &self.fg.label)));
+        }
+        return Ok(());
+    }
+
+    async fn listFactors(&self, filter: &ListFactorsFilter) -> Result<Vec<String>, Box<dyn Error>> {
+        return services::post_list_factors(self, filter).await;
+    }
+
+    async fn getNeighbors(&self, label: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        return match services::post_get_neighbors(self, label).await? {
+            Some(nbrs) => Ok(nbrs),
+            None => Err(not_found(format!("node {} in graph {}", label, &self.fg.label))),
+        };
+    }
+
+    async fn getAdjacency(&self) -> Result<Adjacency, Box<dyn Error>> {
+        return services::post_get_adjacency(self).await;
+    }
+
+    async fn getBlobEntry(&self, variableLabel: &str, label: &str) -> Result<Option<BlobEntry>, Box<dyn Error>> {
+        let id = self.getId(&format!("{}{}", variableLabel, label));
+        let entries = services::post_get_blob_entry(&self.client, id).await?;
+        return Ok(entries.into_iter().next());
+    }
+
+    async fn addBlobEntry(&mut self, variableLabel: &str, entry: &BlobEntry) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_add_variable_entry(self, variableLabel, entry).await;
+    }
+
+    async fn deleteBlobEntry(&mut self, variableLabel: &str, label: &str) -> Result<(), Box<dyn Error>> {
+        let id = self.getId(&format!("{}{}", variableLabel, label));
+        let info = services::post_delete_blobentry(&self.client, id).await?;
+        if info.delete_blob_entries.nodes_deleted == 0 {
+            return Err(not_found(format!("blob entry {} on variable {}", label, variableLabel)));
+        }
+        return Ok(());
+    }
+
+    async fn getPPE(&self, variableLabel: &str, solveKey: &str) -> Result<Option<MeanMaxPPE>, Box<dyn Error>> {
+        return services::post_get_ppe(self, variableLabel, solveKey).await;
+    }
+
+    async fn addPPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_add_ppe(self, variableLabel, ppe).await;
+    }
+
+    async fn updatePPE(&mut self, variableLabel: &str, ppe: &MeanMaxPPE) -> Result<Uuid, Box<dyn Error>> {
+        return services::post_update_ppe(self, variableLabel, ppe).await;
+    }
+
+    async fn deletePPE(&mut self, variableLabel: &str, solveKey: &str) -> Result<(), Box<dyn Error>> {
+        let info = services::post_delete_ppe(self, variableLabel, solveKey).await?;
+        if info.delete_ppes.nodes_deleted == 0 {
+            return Err(not_found(format!("ppe {} on variable {}", solveKey, variableLabel)));
+        }
+        return Ok(());
+    }
+
+    async fn listPPEs(&self, variableLabel: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        return match services::post_list_ppes(self, variableLabel).await? {
+            Some(keys) => Ok(keys),
+            None => Err(not_found(format!("variable {} in graph {}", variableLabel, &self.fg.label))),
+        };
+    }
+}
@@ -4,15 +4,15 @@ use crate::{
     Response,
     GraphQLQuery,
     QueryBody,
-    GetOrg,
-    get_org,
-    post_to_nvaapi,
     to_console_debug,
     to_console_error,
 };
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::{
+    GetOrg,
+    get_org,
+    post_to_nvaapi,
     check_deser,
     NavAbilityClient,
 };
@@ -0,0 +1,516 @@
+
+use std::{
+  cmp::Ordering,
+  collections::BTreeMap,
+  error::Error,
+};
+
+use crate::{
+  Uuid,
+  LocalDFG,
+  FactorGraphStore,
+  VariableDFG,
+  VariableType,
+  MeanMaxPPE,
+  FactorDFG,
+  AnyFactor,
+  PackedDistribution,
+  FullNormal,
+  Pose2,
+  Pose3,
+  Pose2Pose2,
+  Pose3Pose3,
+  PoseGraphFormat,
+  ImportOptions,
+  ImportReport,
+  ValidationError,
+  SDK_VERSION,
+  services::import_local_dfg,
+  utils::{geometry, linalg},
+};
+
+
+const PAGE_SIZE: usize = 100;
+
+/// Scaling between the quaternion vector part used by g2o and the rotation vector, `δq = ω / 2`.
+const QUAT_SCALE: [f64; 6] = [1.0, 1.0, 1.0, 0.5, 0.5, 0.5];
+
+
+fn line_error(
+  lineno: usize,
+  details: String,
+) -> Box<dyn Error> {
+  return Box::new(ValidationError {
+    details: format!("pose graph line {}: {}", lineno, details),
+  });
+}
+
+/// Vertex id and the values following it, e.g. `VERTEX_SE2 id x y θ`.
+fn parse_fields(
+  lineno: usize,
+  tokens: &[&str],
+  nids: usize,
+  nvalues: usize,
+) -> Result<(Vec<u64>, Vec<f64>), Box<dyn Error>> {
+  if tokens.len() != 1 + nids + nvalues {
+    return Err(line_error(lineno, format!("{} expects {} values, got {}", tokens[0], nids + nvalues, tokens.len() - 1)));
+  }
+  let mut ids = Vec::new();
+  for t in &tokens[1..1 + nids] {
+    ids.push(t.parse::<u64>().map_err(|_| line_error(lineno, format!("invalid vertex id {:?}", t)))?);
+  }
+  let mut values = Vec::new();
+  for t in &tokens[1 + nids..] {
+    values.push(t.parse::<f64>().map_err(|_| line_error(lineno, format!("invalid number {:?}", t)))?);
+  }
+  return Ok((ids, values));
+}
+
+/// Full symmetric matrix from its upper triangle stored row by row.
+fn from_upper_triangle(
+  upper: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  let mut a = vec![0.0; n*n];
+  let mut k = 0;
+  for i in 0..n {
+    for j in i..n {
+      a[i*n + j] = upper[k];
+      a[j*n + i] = upper[k];
+      k += 1;
+    }
+  }
+  return a;
+}
+
+fn to_upper_triangle(
+  a: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  let mut upper = Vec::new();
+  for i in 0..n {
+    for j in i..n {
+      upper.push(a[i*n + j]);
+    }
+  }
+  return upper;
+}
+
+/// Returns `diag(d) * a * diag(d)`.
+fn scale_symmetric(
+  a: &[f64],
+  d: &[f64],
+) -> Vec<f64> {
+  let n = d.len();
+  return (0..n*n).map(|k| d[k / n] * a[k] * d[k % n]).collect();
+}
+
+/// TORO orders the 2D information matrix as `xx xy yy θθ xθ yθ`.
+fn toro_to_g2o_info2(
+  v: &[f64],
+) -> Vec<f64> {
+  return vec![v[0], v[1], v[4], v[2], v[5], v[3]];
+}
+
+fn g2o_to_toro_info2(
+  v: &[f64],
+) -> Vec<f64> {
+  return vec![v[0], v[1], v[3], v[5], v[2], v[4]];
+}
+
+fn vertex_label(
+  id: u64,
+) -> String {
+  return format!("x{}", id);
+}
+
+fn add_vertex<V: VariableType>(
+  dfg: &mut LocalDFG,
+  lineno: usize,
+  id: u64,
+  variableType: V,
+  coords: Vec<f64>,
+) -> Result<(), Box<dyn Error>> {
+  let label = vertex_label(id);
+  if dfg.variables.contains_key(&label) {
+    return Err(line_error(lineno, format!("duplicate vertex {}", id)));
+  }
+  let mut variable = VariableDFG::new(&label, variableType, None, None);
+  variable.ppes.push(MeanMaxPPE::new(
+    "default", coords.clone(), coords.clone(), coords, "MeanMaxPPE", SDK_VERSION, None, None
+  ));
+  dfg.add_variable_dfg(variable)?;
+  return Ok(());
+}
+
+
+/// Either odometry factor type, so 2D and 3D edges can be collected together.
+enum PoseEdge {
+  Pose2(Pose2Pose2<FullNormal>),
+  Pose3(Pose3Pose3<FullNormal>),
+}
+
+impl PoseEdge {
+  fn into_factor(
+    self,
+    labels: Vec<String>,
+    seq: usize,
+  ) -> Result<FactorDFG<AnyFactor>, Box<dyn Error>> {
+    let seqkey = seq.to_string();
+    return match self {
+      PoseEdge::Pose2(f) => FactorDFG::new(labels, f, vec![], None, None)?.with_deterministic_label(&seqkey).to_any(),
+      PoseEdge::Pose3(f) => FactorDFG::new(labels, f, vec![], None, None)?.with_deterministic_label(&seqkey).to_any(),
+    };
+  }
+}
+
+
+/// Read a g2o or TORO pose graph into a local graph, e.g. to inspect it or to upload it with
+/// `import_local_dfg`.  Vertex `i` becomes the `Pose2` or `Pose3` variable `xi` with its estimate
+/// as the `"default"` PPE, and each edge a `Pose2Pose2` or `Pose3Pose3` factor with a `FullNormal`
+/// measurement from the information matrix.  Factor labels are deterministic, so reading the same
+/// file again gives the same graph.  3D rotations are converted to rotation vectors, as used by
+/// `Pose3` in Caesar.jl, and the information matrix accordingly.  Other lines, such as `FIX`, are
+/// ignored.
+pub fn read_pose_graph(
+  text: &str,
+  format: PoseGraphFormat,
+) -> Result<LocalDFG, Box<dyn Error>> {
+  let mut dfg = LocalDFG::new(Uuid::nil(), "posegraph");
+  let mut edges = Vec::new();
+
+  for (i, line) in text.lines().enumerate() {
+    let lineno = i + 1;
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.is_empty() || tokens[0].starts_with('#') {
+      continue;
+    }
+    match (format, tokens[0]) {
+      (PoseGraphFormat::G2o, "VERTEX_SE2") | (PoseGraphFormat::Toro, "VERTEX2") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 1, 3)?;
+        add_vertex(&mut dfg, lineno, ids[0], Pose2, v)?;
+      },
+      (PoseGraphFormat::G2o, "VERTEX_SE3:QUAT") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 1, 7)?;
+        let pose = geometry::SE3::new([v[0], v[1], v[2]], [v[6], v[3], v[4], v[5]]);
+        add_vertex(&mut dfg, lineno, ids[0], Pose3, pose.to_vec())?;
+      },
+      (PoseGraphFormat::Toro, "VERTEX3") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 1, 6)?;
+        let pose = geometry::SE3::new([v[0], v[1], v[2]], geometry::euler_to_quat(&[v[3], v[4], v[5]]));
+        add_vertex(&mut dfg, lineno, ids[0], Pose3, pose.to_vec())?;
+      },
+      (PoseGraphFormat::G2o, "EDGE_SE2") | (PoseGraphFormat::Toro, "EDGE2") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 2, 9)?;
+        let upper = if format == PoseGraphFormat::Toro { toro_to_g2o_info2(&v[3..]) } else { v[3..].to_vec() };
+        let z = FullNormal::from_information_matrix(v[..3].to_vec(), from_upper_triangle(&upper, 3))
+          .map_err(|e| line_error(lineno, e.to_string()))?;
+        edges.push((lineno, ids, PoseEdge::Pose2(Pose2Pose2::new(z))));
+      },
+      (PoseGraphFormat::G2o, "EDGE_SE3:QUAT") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 2, 28)?;
+        let w = geometry::so3_log(&[v[6], v[3], v[4], v[5]]);
+        let info = scale_symmetric(&from_upper_triangle(&v[7..], 6), &QUAT_SCALE);
+        let z = FullNormal::from_information_matrix(vec![v[0], v[1], v[2], w[0], w[1], w[2]], info)
+          .map_err(|e| line_error(lineno, e.to_string()))?;
+        edges.push((lineno, ids, PoseEdge::Pose3(Pose3Pose3::new(z))));
+      },
+      (PoseGraphFormat::Toro, "EDGE3") => {
+        let (ids, v) = parse_fields(lineno, &tokens, 2, 27)?;
+        let z = FullNormal::from_information_matrix(v[..6].to_vec(), from_upper_triangle(&v[6..], 6))
+          .and_then(|z| z.pose3_euler_to_rotvec())
+          .map_err(|e| line_error(lineno, e.to_string()))?;
+        edges.push((lineno, ids, PoseEdge::Pose3(Pose3Pose3::new(z))));
+      },
+      _ => {},
+    }
+  }
+
+  for (k, (lineno, ids, factor)) in edges.into_iter().enumerate() {
+    let labels: Vec<String> = ids.iter().map(|id| vertex_label(*id)).collect();
+    for label in &labels {
+      if !dfg.variables.contains_key(label) {
+        return Err(line_error(lineno, format!("edge connects unknown vertex {}", label)));
+      }
+    }
+    dfg.add_factor(factor.into_factor(labels, k)?)?;
+  }
+  return Ok(dfg);
+}
+
+
+/// Read a g2o or TORO pose graph, see `read_pose_graph`, and add it to a local graph or
+/// `NavAbilityDFG` through the batched add path, see `import_local_dfg`.
+pub async fn import_pose_graph<G: FactorGraphStore>(
+  dfg: &mut G,
+  text: &str,
+  format: PoseGraphFormat,
+  options: &ImportOptions,
+) -> Result<ImportReport, Box<dyn Error>> {
+  return import_local_dfg(dfg, &read_pose_graph(text, format)?, options).await;
+}
+
+
+/// Natural ordering of labels, runs of digits compare by value, e.g. `x2` before `x10`.
+fn natural_cmp(
+  a: &str,
+  b: &str,
+) -> Ordering {
+  let (mut a, mut b) = (a, b);
+  while !a.is_empty() && !b.is_empty() {
+    let na = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+    let nb = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+    let ord = if 0 < na && 0 < nb {
+      // compare numbers by length without leading zeros first, so long runs do not overflow
+      let (da, db) = (a[..na].trim_start_matches('0'), b[..nb].trim_start_matches('0'));
+      da.len().cmp(&db.len()).then(da.cmp(db)).then(na.cmp(&nb))
+    } else {
+      let (ca, cb) = (a.chars().next().unwrap(), b.chars().next().unwrap());
+      let ord = ca.cmp(&cb);
+      if ord == Ordering::Equal {
+        a = &a[ca.len_utf8()..];
+        b = &b[cb.len_utf8()..];
+        continue;
+      }
+      ord
+    };
+    if ord != Ordering::Equal {
+      return ord;
+    }
+    a = &a[na..];
+    b = &b[nb..];
+  }
+  return a.len().cmp(&b.len());
+}
+
+/// Vertex ids for the variables, the number at the end of each label if these are unique, e.g.
+/// `x12` is vertex 12, otherwise consecutive ids in label order.
+fn vertex_ids(
+  labels: &[String],
+) -> BTreeMap<String, u64> {
+  let mut ids = BTreeMap::new();
+  for label in labels {
+    let digits = label.trim_start_matches(|c: char| !c.is_ascii_digit());
+    match digits.parse::<u64>() {
+      Ok(id) if !ids.values().any(|v| *v == id) => { ids.insert(label.clone(), id); },
+      _ => break,
+    }
+  }
+  if ids.len() != labels.len() {
+    return labels.iter().enumerate().map(|(i, l)| (l.clone(), i as u64)).collect();
+  }
+  return ids;
+}
+
+fn join(
+  values: &[f64],
+) -> String {
+  return values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" ");
+}
+
+/// Information matrix of a `FullNormal` measurement.
+fn information(
+  label: &str,
+  cov: &[f64],
+  n: usize,
+) -> Result<Vec<f64>, Box<dyn Error>> {
+  if cov.len() != n*n {
+    return Err(Box::new(ValidationError {
+      details: format!("factor {} has a {} entry covariance, expected {}x{}", label, cov.len(), n, n),
+    }));
+  }
+  let l = linalg::cholesky(cov, n).map_err(|i| -> Box<dyn Error> {
+    Box::new(ValidationError {
+      details: format!("factor {} covariance is not positive definite at diagonal entry ({},{})", label, i, i),
+    })
+  })?;
+  return Ok(linalg::cholesky_inverse(&l, n));
+}
+
+
+/// Write the `Pose2` and `Pose3` variables and the `Pose2Pose2` and `Pose3Pose3` factors with a
+/// `FullNormal` measurement of a graph as g2o or TORO, the inverse of `read_pose_graph`.  Vertex
+/// estimates are taken from the `"default"` PPE, or the origin if there is none.  Vertices are
+/// written in natural label order, i.e. `x2` before `x10`.  Other variables and factors, e.g.
+/// priors, are skipped.
+pub async fn write_pose_graph<G: FactorGraphStore>(
+  dfg: &G,
+  format: PoseGraphFormat,
+) -> Result<String, Box<dyn Error>> {
+  let mut poses = Vec::new();
+  for page in dfg.listVariables().await?.chunks(PAGE_SIZE) {
+    for v in dfg.getVariables(page).await? {
+      if v.variableType == Pose2.type_str() || v.variableType == Pose3.type_str() {
+        poses.push(v);
+      }
+    }
+  }
+  poses.sort_by(|a, b| natural_cmp(&a.label, &b.label));
+  let ids = vertex_ids(&poses.iter().map(|v| v.label.clone()).collect::<Vec<String>>());
+
+  let mut out = String::new();
+  for v in &poses {
+    let is2d = v.variableType == Pose2.type_str();
+    let dim = if is2d { 3 } else { 6 };
+    let coords = v.ppes.iter()
+      .find(|p| p.solveKey == "default" && p.suggested.len() == dim)
+      .map(|p| p.suggested.clone())
+      .unwrap_or(vec![0.0; dim]);
+    let line = match (format, is2d) {
+      (PoseGraphFormat::G2o, true) => format!("VERTEX_SE2 {} {}", ids[&v.label], join(&coords)),
+      (PoseGraphFormat::Toro, true) => format!("VERTEX2 {} {}", ids[&v.label], join(&coords)),
+      (PoseGraphFormat::G2o, false) => {
+        let q = geometry::so3_exp(&[coords[3], coords[4], coords[5]]);
+        format!("VERTEX_SE3:QUAT {} {} {} {} {} {}", ids[&v.label], join(&coords[..3]), q[1], q[2], q[3], q[0])
+      },
+      (PoseGraphFormat::Toro, false) => {
+        let rpy = geometry::quat_to_euler(&geometry::so3_exp(&[coords[3], coords[4], coords[5]]));
+        format!("VERTEX3 {} {} {}", ids[&v.label], join(&coords[..3]), join(&rpy))
+      },
+    };
+    out.push_str(&line);
+    out.push('\n');
+  }
+
+  let mut factors = Vec::new();
+  for page in dfg.lsf().await?.chunks(PAGE_SIZE) {
+    factors.extend(dfg.getFactors(page).await?);
+  }
+  factors.sort_by(|a, b| natural_cmp(&a.label, &b.label));
+  for f in factors {
+    let label = f.label.clone();
+    let (z, is2d) = match &f.fnctype {
+      AnyFactor::Pose2Pose2(p) => (&p.Z, true),
+      AnyFactor::Pose3Pose3(p) => (&p.Z, false),
+      _ => continue,
+    };
+    let z = match z {
+      PackedDistribution::FullNormal(z) => z,
+      _ => continue,
+    };
+    let ends: Option<Vec<u64>> = f.variableOrderSymbols_.iter().map(|l| ids.get(l).copied()).collect();
+    let ends = match ends {
+      Some(ends) if ends.len() == 2 => ends,
+      _ => continue,
+    };
+    let dim = if is2d { 3 } else { 6 };
+    if z.mu.len() != dim {
+      return Err(Box::new(ValidationError {
+        details: format!("factor {} has a {} dimensional measurement, expected {}", &label, z.mu.len(), dim),
+      }));
+    }
+    let line = match (format, is2d) {
+      (PoseGraphFormat::G2o, true) => {
+        let info = information(&label, &z.cov, 3)?;
+        format!("EDGE_SE2 {} {} {} {}", ends[0], ends[1], join(&z.mu), join(&to_upper_triangle(&info, 3)))
+      },
+      (PoseGraphFormat::Toro, true) => {
+        let info = information(&label, &z.cov, 3)?;
+        format!("EDGE2 {} {} {} {}", ends[0], ends[1], join(&z.mu), join(&g2o_to_toro_info2(&to_upper_triangle(&info, 3))))
+      },
+      (PoseGraphFormat::G2o, false) => {
+        let q = geometry::so3_exp(&[z.mu[3], z.mu[4], z.mu[5]]);
+        let unscale: Vec<f64> = QUAT_SCALE.iter().map(|d| 1.0 / d).collect();
+        let info = scale_symmetric(&information(&label, &z.cov, 6)?, &unscale);
+        format!(
+          "EDGE_SE3:QUAT {} {} {} {} {} {} {} {}",
+          ends[0], ends[1], join(&z.mu[..3]), q[1], q[2], q[3], q[0], join(&to_upper_triangle(&info, 6))
+        )
+      },
+      (PoseGraphFormat::Toro, false) => {
+        let euler = FullNormal::new(z.mu.clone(), z.cov.clone())?.pose3_rotvec_to_euler()?;
+        let info = information(&label, &euler.cov, 6)?;
+        format!("EDGE3 {} {} {} {}", ends[0], ends[1], join(&euler.mu), join(&to_upper_triangle(&info, 6)))
+      },
+    };
+    out.push_str(&line);
+    out.push('\n');
+  }
+  return Ok(out);
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(
+    a: &[f64],
+    b: &[f64],
+    tol: f64,
+  ) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+      assert!((x - y).abs() <= tol * (1.0 + x.abs().max(y.abs())), "entry {}: {} != {}\n{:?}\n{:?}", i, x, y, a, b);
+    }
+  }
+
+  fn pose3_measurement(
+    dfg: &LocalDFG,
+  ) -> (Vec<f64>, Vec<f64>) {
+    assert_eq!(dfg.factors.len(), 1);
+    let f = dfg.factors.values().next().unwrap();
+    assert_eq!(f.variableOrderSymbols_, vec!["x2", "x10"]);
+    return match &f.fnctype {
+      AnyFactor::Pose3Pose3(p) => match &p.Z {
+        PackedDistribution::FullNormal(z) => (z.mu.clone(), information(&f.label, &z.cov, 6).unwrap()),
+        z => panic!("unexpected measurement {:?}", z),
+      },
+      f => panic!("unexpected factor {:?}", f),
+    };
+  }
+
+  fn g2o_info_upper() -> Vec<f64> {
+    let mut info = vec![0.0; 36];
+    for i in 0..6 {
+      info[i*6 + i] = if i < 3 { 100.0 } else { 400.0 };
+    }
+    info[3] = 5.0;
+    info[18] = 5.0;
+    return to_upper_triangle(&info, 6);
+  }
+
+  #[test]
+  fn test_natural_cmp() {
+    let mut labels = vec!["x10", "x2", "l1", "x1b", "x1", "x"];
+    labels.sort_by(|a, b| natural_cmp(a, b));
+    assert_eq!(labels, vec!["l1", "x", "x1", "x1b", "x2", "x10"]);
+  }
+
+  #[test]
+  fn test_g2o_roundtrip() {
+    let (s, c) = (0.15f64.sin(), 0.15f64.cos());
+    let text = format!(
+      "VERTEX_SE3:QUAT 10 1 0 0 0 0 {} {}\nVERTEX_SE3:QUAT 2 0 0 0 0 0 0 1\nEDGE_SE3:QUAT 2 10 1 0 0 0 0 {} {} {}\n",
+      s, c, s, c, join(&g2o_info_upper()),
+    );
+
+    let dfg = read_pose_graph(&text, PoseGraphFormat::G2o).unwrap();
+    let (mu, info) = pose3_measurement(&dfg);
+    // rotation vector about z, twice the vector part of the quaternion for small angles
+    assert_close(&mu, &[1.0, 0.0, 0.0, 0.0, 0.0, 0.3], 1e-12);
+    // the rotation block scales with QUAT_SCALE², the translation-rotation block with QUAT_SCALE
+    let mut expected = vec![0.0; 36];
+    for i in 0..6 {
+      expected[i*6 + i] = 100.0;
+    }
+    expected[3] = 2.5;
+    expected[18] = 2.5;
+    assert_close(&info, &expected, 1e-9);
+
+    let written = crate::block_on_ready(write_pose_graph(&dfg, PoseGraphFormat::G2o)).unwrap();
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("VERTEX_SE3:QUAT 2 "), "{}", written);
+    assert!(lines[1].starts_with("VERTEX_SE3:QUAT 10 "), "{}", written);
+    let edge: Vec<f64> = lines[2].split_whitespace().skip(3).map(|t| t.parse().unwrap()).collect();
+    assert_close(&edge[..7], &[1.0, 0.0, 0.0, 0.0, 0.0, s, c], 1e-12);
+    assert_close(&edge[7..], &g2o_info_upper(), 1e-9);
+
+    let reread = read_pose_graph(&written, PoseGraphFormat::G2o).unwrap();
+    let (mu2, info2) = pose3_measurement(&reread);
+    assert_close(&mu2, &mu, 1e-12);
+    assert_close(&info2, &info, 1e-9);
+    assert_close(&reread.variables["x10"].ppes[0].suggested, &dfg.variables["x10"].ppes[0].suggested, 1e-12);
+  }
+}
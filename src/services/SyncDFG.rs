@@ -0,0 +1,309 @@
+
+use std::{
+  collections::BTreeMap,
+  error::Error,
+};
+
+use crate::{
+  AnyFactor,
+  ConflictPolicy,
+  DiffNode,
+  DiffNodeType,
+  FactorDFG,
+  FactorGraphStore,
+  GetId,
+  GraphDiff,
+  SyncReport,
+  Utc,
+  Uuid,
+  VariableDFG,
+};
+
+
+const PAGE_SIZE: usize = 100;
+
+/// Variables and factors of a graph, read once in pages for comparing.
+struct Snapshot {
+  variables: BTreeMap<String, VariableDFG>,
+  factors: BTreeMap<String, FactorDFG<AnyFactor>>,
+}
+
+impl Snapshot {
+  async fn read<G: FactorGraphStore>(
+    dfg: &G,
+  ) -> Result<Self, Box<dyn Error>> {
+    let mut snap = Snapshot { variables: BTreeMap::new(), factors: BTreeMap::new() };
+    for page in dfg.listVariables().await?.chunks(PAGE_SIZE) {
+      for v in dfg.getVariables(page).await? {
+        snap.variables.insert(v.label.clone(), v);
+      }
+    }
+    for page in dfg.lsf().await?.chunks(PAGE_SIZE) {
+      for f in dfg.getFactors(page).await? {
+        snap.factors.insert(f.label.clone(), f);
+      }
+    }
+    return Ok(snap);
+  }
+
+  /// All nodes keyed by their deterministic id, with the time they were last updated.
+  fn nodes<G: GetId>(
+    &self,
+    dfg: &G,
+  ) -> BTreeMap<Uuid, (DiffNodeType, String, Option<String>, Option<chrono::DateTime<Utc>>)> {
+    let mut nodes = BTreeMap::new();
+    for v in self.variables.values() {
+      nodes.insert(dfg.getId(&v.label), (DiffNodeType::Variable, v.label.clone(), None, v.lastUpdatedTimestamp));
+      for ppe in &v.ppes {
+        nodes.insert(
+          dfg.getId(&format!("{}{}", &v.label, &ppe.solveKey)),
+          (DiffNodeType::PPE, v.label.clone(), Some(ppe.solveKey.clone()), ppe.lastUpdatedTimestamp),
+        );
+      }
+      for entry in &v.blobEntries {
+        nodes.insert(
+          dfg.getId(&format!("{}{}", &v.label, &entry.label)),
+          (DiffNodeType::BlobEntry, v.label.clone(), Some(entry.label.clone()), entry.lastUpdatedTimestamp),
+        );
+      }
+    }
+    for f in self.factors.values() {
+      nodes.insert(dfg.getId(&f.label), (DiffNodeType::Factor, f.label.clone(), None, f.lastUpdatedTimestamp));
+    }
+    return nodes;
+  }
+
+  fn variable(
+    &self,
+    node: &DiffNode,
+  ) -> Option<&VariableDFG> {
+    return self.variables.get(&node.label);
+  }
+}
+
+
+/// True if a node present in both graphs holds the same content, ids and timestamps are ignored.
+fn same_content(
+  local: &Snapshot,
+  remote: &Snapshot,
+  node: &DiffNode,
+) -> bool {
+  let key = node.key.clone().unwrap_or_default();
+  return match node.node_type {
+    DiffNodeType::Variable => match (local.variable(node), remote.variable(node)) {
+      (Some(a), Some(b)) => a.variableType == b.variableType
+        && a.tags == b.tags
+        && a.solvable == b.solvable
+        && a.metadata == b.metadata
+        && a.nstime == b.nstime,
+      _ => false,
+    },
+    DiffNodeType::Factor => match (local.factors.get(&node.label), remote.factors.get(&node.label)) {
+      (Some(a), Some(b)) => a.is_same_factor(b) && a.tags == b.tags && a.solvable == b.solvable,
+      _ => false,
+    },
+    DiffNodeType::PPE => {
+      let a = local.variable(node).and_then(|v| v.ppes.iter().find(|p| p.solveKey == key));
+      let b = remote.variable(node).and_then(|v| v.ppes.iter().find(|p| p.solveKey == key));
+      match (a, b) {
+        (Some(a), Some(b)) => a.suggested == b.suggested && a.mean == b.mean && a.max == b.max,
+        _ => false,
+      }
+    },
+    DiffNodeType::BlobEntry => {
+      let a = local.variable(node).and_then(|v| v.blobEntries.iter().find(|be| be.label == key));
+      let b = remote.variable(node).and_then(|v| v.blobEntries.iter().find(|be| be.label == key));
+      match (a, b) {
+        (Some(a), Some(b)) => a.blobId == b.blobId
+          && a.hash == b.hash
+          && a.mimeType == b.mimeType
+          && a.metadata == b.metadata,
+        _ => false,
+      }
+    },
+  };
+}
+
+
+fn diff_snapshots<L: GetId, R: GetId>(
+  local: &L,
+  local_snap: &Snapshot,
+  remote: &R,
+  remote_snap: &Snapshot,
+) -> GraphDiff {
+  let local_nodes = local_snap.nodes(local);
+  let mut remote_nodes = remote_snap.nodes(remote);
+
+  let mut diff = GraphDiff::default();
+  for (id, (node_type, label, key, updated)) in local_nodes {
+    let mut node = DiffNode {
+      id,
+      node_type,
+      label,
+      key,
+      local_updated: updated,
+      remote_updated: None,
+    };
+    match remote_nodes.remove(&id) {
+      None => diff.added.push(node),
+      Some((_, _, _, remote_updated)) => {
+        node.remote_updated = remote_updated;
+        if !same_content(local_snap, remote_snap, &node) {
+          diff.changed.push(node);
+        }
+      },
+    }
+  }
+  for (id, (node_type, label, key, updated)) in remote_nodes {
+    diff.removed.push(DiffNode {
+      id,
+      node_type,
+      label,
+      key,
+      local_updated: None,
+      remote_updated: updated,
+    });
+  }
+  return diff;
+}
+
+
+/// Compare a local and a remote graph node by node, i.e. variables, factors, PPEs and blob
+/// entries matched by their deterministic id.  `added` nodes only exist locally, `removed` nodes
+/// only remotely, and `changed` nodes exist in both but differ in content, with their
+/// `lastUpdatedTimestamp`s to tell which side is newer.  Ids only match if the local graph uses
+/// the namespace and label of the remote one, e.g.
+/// `LocalDFG::new(nvafg.fg.namespace, &nvafg.fg.label)`.
+pub async fn diff<L, R>(
+  local: &L,
+  remote: &R,
+) -> Result<GraphDiff, Box<dyn Error>>
+where
+  L: FactorGraphStore + GetId,
+  R: FactorGraphStore + GetId,
+{
+  let local_snap = Snapshot::read(local).await?;
+  let remote_snap = Snapshot::read(remote).await?;
+  return Ok(diff_snapshots(local, &local_snap, remote, &remote_snap));
+}
+
+
+/// Write one node from `source` to `target`, replacing it if `target` already has it.
+async fn copy_node<G: FactorGraphStore>(
+  target: &mut G,
+  target_snap: &Snapshot,
+  source_snap: &Snapshot,
+  node: &DiffNode,
+) -> Result<(), Box<dyn Error>> {
+  let key = node.key.clone().unwrap_or_default();
+  let (source_var, target_var) = (source_snap.variable(node), target_snap.variable(node));
+  match node.node_type {
+    DiffNodeType::Variable => {
+      let mut v = source_var.cloned().ok_or("variable missing from sync source")?;
+      v.id = None;
+      // PPEs and blob entries are synced separately, `addVariable` and `updateVariable` leave them alone
+      if target_var.is_some() {
+        target.updateVariable(&v).await?;
+      } else {
+        target.addVariable(&v).await?;
+      }
+    },
+    DiffNodeType::PPE => {
+      let mut ppe = source_var.and_then(|v| v.ppes.iter().find(|p| p.solveKey == key)).cloned()
+        .ok_or("ppe missing from sync source")?;
+      ppe.id = None;
+      if target_var.map_or(false, |v| v.ppes.iter().any(|p| p.solveKey == key)) {
+        target.updatePPE(&node.label, &ppe).await?;
+      } else {
+        target.addPPE(&node.label, &ppe).await?;
+      }
+    },
+    DiffNodeType::BlobEntry => {
+      let mut entry = source_var.and_then(|v| v.blobEntries.iter().find(|be| be.label == key)).cloned()
+        .ok_or("blob entry missing from sync source")?;
+      entry.id = None;
+      // blob entries can not be updated, replace them
+      if target_var.map_or(false, |v| v.blobEntries.iter().any(|be| be.label == key)) {
+        target.deleteBlobEntry(&node.label, &key).await?;
+      }
+      target.addBlobEntry(&node.label, &entry).await?;
+    },
+    DiffNodeType::Factor => {
+      let mut f = source_snap.factors.get(&node.label).cloned().ok_or("factor missing from sync source")?;
+      f.id = None;
+      match target_snap.factors.get(&node.label) {
+        Some(existing) if existing.variableOrderSymbols_ == f.variableOrderSymbols_ => {
+          target.updateFactor(&f).await?;
+        },
+        Some(_) => {
+          // the variables of a factor can not be updated
+          target.deleteFactor(&node.label).await?;
+          target.addFactor(f).await?;
+        },
+        None => {
+          target.addFactor(f).await?;
+        },
+      }
+    },
+  }
+  return Ok(());
+}
+
+
+/// Bring a local and a remote graph to the same content, see `diff`.  Nodes only in the local
+/// graph are pushed, nodes only in the remote graph are pulled, and nodes changed in both are
+/// resolved by `policy`.  Nothing is deleted, so a node deleted on one side comes back from the
+/// other.  Changed blob entries are replaced, i.e. deleted and added again, the blobs themselves
+/// are not copied.
+pub async fn sync<L, R>(
+  local: &mut L,
+  remote: &mut R,
+  policy: ConflictPolicy,
+) -> Result<SyncReport, Box<dyn Error>>
+where
+  L: FactorGraphStore + GetId,
+  R: FactorGraphStore + GetId,
+{
+  let local_snap = Snapshot::read(local).await?;
+  let remote_snap = Snapshot::read(remote).await?;
+  let diff = diff_snapshots(local, &local_snap, remote, &remote_snap);
+
+  let mut report = SyncReport::default();
+  report.pushed = diff.added;
+  report.pulled = diff.removed;
+  for node in diff.changed {
+    let push = match policy {
+      ConflictPolicy::LocalWins => true,
+      ConflictPolicy::RemoteWins => false,
+      ConflictPolicy::Newest => match (node.local_updated, node.remote_updated) {
+        (Some(local_updated), Some(remote_updated)) => local_updated >= remote_updated,
+        // no timestamp means changed since the last write, e.g. edited in a `LocalDFG`
+        (None, _) => true,
+        (Some(_), None) => false,
+      },
+    };
+    if push {
+      report.pushed.push(node);
+    } else {
+      report.pulled.push(node);
+    }
+  }
+
+  // variables before their PPEs, blob entries and factors
+  let rank = |node: &DiffNode| match node.node_type {
+    DiffNodeType::Variable => 0,
+    DiffNodeType::PPE => 1,
+    DiffNodeType::BlobEntry => 2,
+    DiffNodeType::Factor => 3,
+  };
+  report.pushed.sort_by_key(rank);
+  report.pulled.sort_by_key(rank);
+
+  for node in &report.pushed {
+    copy_node(remote, &remote_snap, &local_snap, node).await?;
+  }
+  for node in &report.pulled {
+    copy_node(local, &local_snap, &remote_snap, node).await?;
+  }
+  return Ok(report);
+}
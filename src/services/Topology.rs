@@ -0,0 +1,330 @@
+
+use std::{
+  collections::{BTreeMap, BTreeSet, VecDeque},
+  error::Error,
+};
+
+use crate::{
+  Adjacency,
+  FactorGraphStore,
+  LocalDFG,
+};
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+use crate::{
+  GraphQLQuery,
+  GetId,
+  NavAbilityDFG,
+  GetNeighbors,
+  get_neighbors,
+  GetAdjacency,
+  get_adjacency,
+};
+
+
+impl Adjacency {
+  pub fn new() -> Self {
+    return Self::default();
+  }
+
+  pub fn from_local(
+    dfg: &LocalDFG,
+  ) -> Self {
+    let mut adj = Self::new();
+    for vl in dfg.variables.keys() {
+      adj.insert_variable(vl);
+    }
+    for f in dfg.factors.values() {
+      adj.insert_factor(&f.label, &f.variableOrderSymbols_);
+    }
+    return adj;
+  }
+
+  pub fn insert_variable(
+    &mut self,
+    label: &str,
+  ) {
+    self.variables.insert(label.to_owned());
+    self.adjacency.entry(label.to_owned()).or_default();
+  }
+
+  /// Insert a factor and connect it to its variables, which are inserted if missing.
+  pub fn insert_factor(
+    &mut self,
+    label: &str,
+    variables: &Vec<String>,
+  ) {
+    self.factors.insert(label.to_owned());
+    for vl in variables {
+      self.insert_variable(vl);
+      self.adjacency.entry(vl.to_owned()).or_default().insert(label.to_owned());
+    }
+    self.adjacency.entry(label.to_owned()).or_default().extend(variables.iter().cloned());
+  }
+
+  /// Remove a variable or factor and all its connections.
+  pub fn remove_node(
+    &mut self,
+    label: &str,
+  ) {
+    if let Some(nbrs) = self.adjacency.remove(label) {
+      for n in nbrs {
+        if let Some(adj) = self.adjacency.get_mut(&n) {
+          adj.remove(label);
+        }
+      }
+    }
+    self.variables.remove(label);
+    self.factors.remove(label);
+  }
+
+  /// Factors connected to a variable, or variables connected to a factor, `None` if the node is unknown.
+  pub fn neighbors(
+    &self,
+    label: &str,
+  ) -> Option<Vec<String>> {
+    return self.adjacency.get(label).map(|adj| adj.iter().cloned().collect());
+  }
+
+  /// All nodes within `distance` edges of `center`, including `center`, e.g. a distance of 2 from a
+  /// variable gives its factors and their variables.
+  pub fn neighborhood(
+    &self,
+    center: &str,
+    distance: usize,
+  ) -> Vec<String> {
+    return bfs_levels(center, distance, |n| self.neighbors(n).unwrap_or_default())
+      .into_keys()
+      .collect();
+  }
+
+  /// Shortest path from `from` to `to` as the alternating list of variable and factor labels,
+  /// `None` if the nodes are not connected.
+  pub fn shortest_path(
+    &self,
+    from: &str,
+    to: &str,
+  ) -> Option<Vec<String>> {
+    if !self.adjacency.contains_key(from) || !self.adjacency.contains_key(to) {
+      return None;
+    }
+    let mut parent: BTreeMap<String, String> = BTreeMap::new();
+    let mut queue = VecDeque::from([from.to_owned()]);
+    parent.insert(from.to_owned(), from.to_owned());
+    while let Some(n) = queue.pop_front() {
+      if n == to {
+        let mut path = vec![n];
+        while path[path.len() - 1] != from {
+          path.push(parent[&path[path.len() - 1]].clone());
+        }
+        path.reverse();
+        return Some(path);
+      }
+      for m in &self.adjacency[&n] {
+        if !parent.contains_key(m) {
+          parent.insert(m.clone(), n.clone());
+          queue.push_back(m.clone());
+        }
+      }
+    }
+    return None;
+  }
+
+  /// True if every node can be reached from every other node, an empty graph is connected.
+  pub fn is_connected(&self) -> bool {
+    return match self.adjacency.keys().next() {
+      Some(first) => self.neighborhood(first, usize::MAX).len() == self.adjacency.len(),
+      None => true,
+    };
+  }
+}
+
+
+/// Breadth first search up to `distance`, returns the nodes found with their distance from `center`.
+fn bfs_levels<N: Fn(&str) -> Vec<String>>(
+  center: &str,
+  distance: usize,
+  neighbors: N,
+) -> BTreeMap<String, usize> {
+  let mut seen = BTreeMap::from([(center.to_owned(), 0)]);
+  let mut frontier = vec![center.to_owned()];
+  let mut d = 0;
+  while d < distance && !frontier.is_empty() {
+    d += 1;
+    let mut next = Vec::new();
+    for n in frontier {
+      for m in neighbors(&n) {
+        if !seen.contains_key(&m) {
+          seen.insert(m.clone(), d);
+          next.push(m);
+        }
+      }
+    }
+    frontier = next;
+  }
+  return seen;
+}
+
+
+// ===================== Queries =========================
+
+
+/// Neighbors of a variable or factor using a single server-side query, `None` if no node with
+/// this label exists.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_neighbors(
+  nvafg: &NavAbilityDFG,
+  label: &str,
+) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+
+  let request_body = GetNeighbors::build_query(
+    get_neighbors::Variables {
+      node_id: nvafg.getId(label).to_string(),
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    get_neighbors::Variables,
+    get_neighbors::ResponseData,
+    Option<Vec<String>>
+  >(
+    &nvafg.client,
+    request_body,
+    |s| {
+      if let Some(v) = s.variables.first() {
+        return Some(v.factors.iter().map(|f| f.label.clone()).collect());
+      }
+      if let Some(f) = s.factors.first() {
+        return Some(f.variable_order_symbols.clone());
+      }
+      return None;
+    },
+    Some(3)
+  ).await;
+}
+
+
+/// Fetch the connectivity of the whole graph with a single server-side query.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_adjacency(
+  nvafg: &NavAbilityDFG,
+) -> Result<Adjacency, Box<dyn Error>> {
+
+  let request_body = GetAdjacency::build_query(
+    get_adjacency::Variables {
+      fg_id: nvafg.fg.getId("").to_string(),
+    }
+  );
+
+  return crate::post_to_nvaapi::<
+    get_adjacency::Variables,
+    get_adjacency::ResponseData,
+    Adjacency
+  >(
+    &nvafg.client,
+    request_body,
+    |s| {
+      let mut adj = Adjacency::new();
+      for fg in &s.factorgraphs {
+        for v in &fg.variables {
+          adj.insert_variable(&v.label);
+        }
+        for f in &fg.factors {
+          adj.insert_factor(&f.label, &f.variable_order_symbols);
+        }
+      }
+      return adj;
+    },
+    Some(3)
+  ).await;
+}
+
+
+// ===================== Backend agnostic =========================
+
+
+/// Factors connected to a variable, or variables connected to a factor.
+pub async fn get_neighbors<G: FactorGraphStore>(
+  dfg: &G,
+  label: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+  return dfg.getNeighbors(label).await;
+}
+
+/// Copy of the variables and factors within `distance` edges of `center` into a `LocalDFG`, e.g. a
+/// distance of 2 from a pose gives the pose, its factors and the variables of those factors.
+/// Factors are only included if all their variables are within the distance.  Nodes keep the ids
+/// of the source graph.
+pub async fn get_subgraph<G: FactorGraphStore>(
+  dfg: &G,
+  center: &str,
+  distance: usize,
+) -> Result<LocalDFG, Box<dyn Error>> {
+  let mut sub = LocalDFG::default();
+  sub.label = format!("{}_{}", center, distance);
+  let center_is_variable = match dfg.getVariable(center).await? {
+    Some(v) => {
+      sub.add_variable_dfg(v)?;
+      true
+    },
+    None => false,
+  };
+
+  // expand hop by hop with neighbor queries, so only the neighborhood is fetched, variables and
+  // factors alternate so the hop count tells which kind each node is
+  let mut seen = BTreeSet::from([center.to_owned()]);
+  let mut frontier = vec![center.to_owned()];
+  let mut factor_labels = Vec::new();
+  if !center_is_variable {
+    factor_labels.push(center.to_owned());
+  }
+  for hop in 1..=distance {
+    let is_variable = center_is_variable == (hop % 2 == 0);
+    let mut next = Vec::new();
+    for n in &frontier {
+      for m in dfg.getNeighbors(n).await? {
+        if seen.insert(m.clone()) {
+          next.push(m);
+        }
+      }
+    }
+    for m in &next {
+      if is_variable {
+        if let Some(v) = dfg.getVariable(m).await? {
+          sub.add_variable_dfg(v)?;
+        }
+      } else {
+        factor_labels.push(m.clone());
+      }
+    }
+    frontier = next;
+  }
+
+  for fl in factor_labels {
+    if let Some(f) = dfg.getFactor(&fl).await? {
+      if f.variableOrderSymbols_.iter().all(|v| sub.variables.contains_key(v)) {
+        sub.add_factor(f)?;
+      }
+    }
+  }
+  return Ok(sub);
+}
+
+/// Shortest path between two nodes as the alternating list of variable and factor labels, `None`
+/// if they are not connected.  Reads the adjacency of the whole graph on every call, use
+/// `getAdjacency` and `Adjacency::shortest_path` for repeated queries.
+pub async fn shortest_path<G: FactorGraphStore>(
+  dfg: &G,
+  from: &str,
+  to: &str,
+) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+  return Ok(dfg.getAdjacency().await?.shortest_path(from, to));
+}
+
+/// True if the graph consists of a single connected component, reads the adjacency of the whole
+/// graph on every call, see `shortest_path`.
+pub async fn is_connected<G: FactorGraphStore>(
+  dfg: &G,
+) -> Result<bool, Box<dyn Error>> {
+  return Ok(dfg.getAdjacency().await?.is_connected());
+}
@@ -2,11 +2,21 @@
 use crate::{
     parse_str_utc, 
     BlobEntry,
+    Error,
     MeanMaxPPE, 
     PackedVariableNodeData, 
     Utc, 
     Uuid,
+    ValidationError,
     VariableDFG, 
+    VariableType,
+    Position1,
+    Point2,
+    Point3,
+    Pose2,
+    Pose3,
+    ContinuousScalar,
+    ContinuousEuclid,
     SDK_VERSION
 };
 
@@ -15,7 +25,6 @@ use crate::{
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
 use crate::{
     GQLResponseEmptyError,
-    Error,     
     Sender, 
     Response, 
     NavAbilityDFG,
@@ -28,6 +37,22 @@ use crate::{
     GraphQLQuery,
     ListVariables,
     AddVariable,
+    AddVariables,
+    add_variables,
+    AddSolverData,
+    UpdateSolverData,
+    DeleteSolverData,
+    delete_solver_data,
+    UpdateVariable,
+    DeleteVariable,
+    delete_variable,
+    AddPPE,
+    UpdatePPE,
+    DeletePPE,
+    delete_ppe,
+    GetPPE,
+    ListPPEKeys,
+    AddBlobEntryVariable,
     GetId,
     check_query_response_data,
     get_variable::{
@@ -40,6 +65,124 @@ use crate::{
 };
 
 
+// helper macro to avoid repetition of "basic" impl VariableType
+#[macro_export]
+macro_rules! GenVariableType { 
+    ($T:ident, $M:literal, $D:literal) => {
+        impl VariableType for $T {
+            fn type_str(&self) -> String {
+                return format!("{}.{}", $M, stringify!($T));
+            }
+
+            fn dim(&self) -> usize {
+                return $D;
+            }
+        }
+    }
+}
+
+
+GenVariableType!(Point2, "RoME", 2);
+GenVariableType!(Point3, "RoME", 3);
+GenVariableType!(Pose2, "RoME", 3);
+GenVariableType!(Pose3, "RoME", 6);
+
+
+impl VariableType for Position1 {
+    fn type_str(&self) -> String {
+        return "IncrementalInference.Position{1}".to_owned();
+    }
+
+    fn dim(&self) -> usize {
+        return 1;
+    }
+}
+
+impl VariableType for ContinuousScalar {
+    fn type_str(&self) -> String {
+        return Position1.type_str();
+    }
+
+    fn dim(&self) -> usize {
+        return 1;
+    }
+}
+
+impl<const N: usize> VariableType for ContinuousEuclid<N> {
+    fn type_str(&self) -> String {
+        return format!("IncrementalInference.Position{{{}}}", N);
+    }
+
+    fn dim(&self) -> usize {
+        return N;
+    }
+}
+
+
+// helper macro, the ppe_fields GQL fragment is generated separately for every query using it
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+macro_rules! MeanMaxPPE_from_gql { 
+    ($T:ty) => {
+        impl From<&$T> for MeanMaxPPE {
+            fn from(
+                ppe: &$T
+            ) -> Self {
+                let mut ppesugg = Vec::new();
+                if let Some(ps) = &ppe.suggested {
+                    for p in ps.iter() {
+                        if p.is_some() { ppesugg.push(p.unwrap().clone()); }
+                    }
+                }
+                let mut ppemax = Vec::new();
+                if let Some(ps) = &ppe.max {
+                    for p in ps.iter() {
+                        if p.is_some() { ppemax.push(p.unwrap().clone()); }
+                    }
+                }
+                let mut ppemean = Vec::new();
+                if let Some(ps) = &ppe.mean {
+                    for p in ps.iter() {
+                        if p.is_some() { ppemean.push(p.unwrap().clone()); }
+                    }
+                }
+                let mut ppe_struct = MeanMaxPPE {
+                    id: None,
+                    solveKey: ppe.solve_key.clone(),
+                    suggested: ppesugg,
+                    max: ppemax,
+                    mean: ppemean,
+                    _type: ppe.type_.clone(),
+                    _version: ppe.version.clone(),
+                    createdTimestamp: None,
+                    lastUpdatedTimestamp: None,
+                };
+                if let Ok(id) = Uuid::parse_str(
+                    &ppe.id
+                ) {
+                    ppe_struct.id = Some(id);
+                }
+                if let Ok(dt) = parse_str_utc(
+                    ppe.created_timestamp.clone()
+                ) {
+                    ppe_struct.createdTimestamp = Some(dt);
+                }
+                if let Ok(dt) = parse_str_utc(
+                    ppe.last_updated_timestamp.clone()
+                ) {
+                    ppe_struct.lastUpdatedTimestamp = Some(dt);
+                }
+                return ppe_struct;
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+MeanMaxPPE_from_gql!(ppe_fields);
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+MeanMaxPPE_from_gql!(crate::get_ppe::ppe_fields);
+
+
 #[allow(non_snake_case)]
 impl MeanMaxPPE {
     pub fn new(
@@ -69,58 +212,29 @@ impl MeanMaxPPE {
     pub fn from_gql(
         ppe: &ppe_fields // FIXME refac to trait similar to BlobEntry_fields
     ) -> Self {
-        let mut ppesugg = Vec::new();
-        if let Some(ps) = &ppe.suggested {
-            for p in ps.iter() {
-                if p.is_some() { ppesugg.push(p.unwrap().clone()); }
-            }
-        }
-        let mut ppemax = Vec::new();
-        if let Some(ps) = &ppe.max {
-            for p in ps.iter() {
-                if p.is_some() { ppemax.push(p.unwrap().clone()); }
-            }
-        }
-        let mut ppemean = Vec::new();
-        if let Some(ps) = &ppe.mean {
-            for p in ps.iter() {
-                if p.is_some() { ppemean.push(p.unwrap().clone()); }
-            }
-        }
-        let mut ppe_struct = MeanMaxPPE {
-            id: None,
-            solveKey: ppe.solve_key.clone(),
-            suggested: ppesugg,
-            max: ppemax,
-            mean: ppemean,
-            _type: ppe.type_.clone(),
-            _version: ppe.version.clone(),
-            createdTimestamp: None,
-            lastUpdatedTimestamp: None,
-        };
-        if let Ok(id) = Uuid::parse_str(
-            &ppe.id
-        ) {
-            ppe_struct.id = Some(id);
-        }
-        if let Ok(dt) = parse_str_utc(
-            ppe.created_timestamp.clone()
-        ) {
-            ppe_struct.createdTimestamp = Some(dt);
-        }
-        if let Ok(dt) = parse_str_utc(
-            ppe.last_updated_timestamp.clone()
-        ) {
-            ppe_struct.lastUpdatedTimestamp = Some(dt);
-        }
-        return ppe_struct;
+        return Self::from(ppe);
     }
 }
 
 
 #[allow(non_snake_case)]
 impl VariableDFG {
-    pub fn new(
+    pub fn new<V: VariableType>(
+        label: &str,
+        variableType: V,
+        timestamp: Option<chrono::DateTime<Utc>>,
+        nstime: Option<usize>,
+    ) -> Self {
+        return Self::new_typestr(
+            label,
+            &variableType.type_str(),
+            timestamp,
+            nstime,
+        );
+    }
+
+    /// Untyped constructor, used when the variable type string comes from the API.
+    pub(crate) fn new_typestr(
         label: &str,
         variableType: &str,
         timestamp: Option<chrono::DateTime<Utc>>,
@@ -140,6 +254,7 @@ impl VariableDFG {
             metadata: "".to_owned(),
             solvable: 1,
             solverData: Vec::new(),
+            lastUpdatedTimestamp: None,
         }
     }
 
@@ -161,7 +276,7 @@ impl VariableDFG {
             None
         };
 
-        let mut variable = Self::new(
+        let mut variable = Self::new_typestr(
             &vgql.variable_skeleton_fields.label.clone(),
             &vgql.variable_summary_fields.variable_type.clone(),
             timestamp,
@@ -184,6 +299,9 @@ impl VariableDFG {
         variable.tags = tags;
 
         variable._version = vgql.variable_summary_fields.version.clone();
+        variable.lastUpdatedTimestamp = parse_str_utc(
+            vgql.variable_summary_fields.last_updated_timestamp.clone()
+        ).ok();
         variable.solvable = vgql.variable_full_fields.solvable as i32;
         if let Some(md) = vgql.variable_full_fields.metadata.clone() {
             variable.metadata = md;
@@ -268,6 +386,40 @@ impl PackedVariableNodeData {
             _version: vndgql.version.to_string(),
         }
     }
+
+    /// Check that the flattened `vecval` and `vecbw` vectors are consistent with `dimval` and `dimbw`,
+    /// i.e. each vector holds a whole number of columns of the stated dimension.
+    #[allow(non_snake_case)]
+    pub fn validate(
+        &self
+    ) -> Result<(), Box<dyn Error>> {
+        let checks = [
+            ("vecval", self.vecval.len(), "dimval", self.dimval),
+            ("vecbw", self.vecbw.len(), "dimbw", self.dimbw),
+        ];
+        for (vname, vlen, dname, dim) in checks {
+            if dim < 0 {
+                return Err(Box::new(ValidationError {
+                    details: format!("PackedVariableNodeData solveKey={:?} has negative {}={}", self.solveKey, dname, dim),
+                }));
+            }
+            let consistent = if dim == 0 { vlen == 0 } else { vlen % (dim as usize) == 0 };
+            if !consistent {
+                return Err(Box::new(ValidationError {
+                    details: format!(
+                        "PackedVariableNodeData solveKey={:?} has {}.len()={} which is not a multiple of {}={}",
+                        self.solveKey, vname, vlen, dname, dim
+                    ),
+                }));
+            }
+        }
+        if self.solveKey.is_empty() {
+            return Err(Box::new(ValidationError {
+                details: "PackedVariableNodeData requires a non-empty solveKey".to_owned(),
+            }));
+        }
+        return Ok(())
+    }
 }
 
 
@@ -279,11 +431,23 @@ pub async fn post_get_variable(
     label: &str,
     fields_full: bool,
 ) -> Result<Option<VariableDFG>, Box<dyn Error>> {
+    let variables = post_get_variables(nvafg, &[label.to_owned()], fields_full).await?;
+    return Ok(variables.into_iter().next());
+}
+
+
+/// Get several variables in one request, labels not in the graph are left out of the result.
+/// Order of the result is not guaranteed, keep pages to a few hundred labels.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_get_variables(
+    nvafg: &NavAbilityDFG,
+    labels: &[String],
+    fields_full: bool,
+) -> Result<Vec<VariableDFG>, Box<dyn Error>> {
 
-    let id = nvafg.fg.getId(label); 
     let request_body = GetVariable::build_query(
         get_variable::Variables {
-            var_id: id.to_string(),
+            var_ids: labels.iter().map(|l| nvafg.fg.getId(l).to_string()).collect(),
             fields_summary: true, // TODO simplify, since this must always be true
             fields_full,
         }
@@ -292,17 +456,11 @@ pub async fn post_get_variable(
     return post_to_nvaapi::<
         get_variable::Variables,
         get_variable::ResponseData,
-        Option<VariableDFG>
+        Vec<VariableDFG>
     >(
         &nvafg.client,
         request_body, 
-        |s| {
-            if 0 < s.variables.len() {
-                // FIXME return the entire list of variables
-                return Some(VariableDFG::from_gql(&s.variables[0]));
-            }
-            return None;
-        },
+        |s| s.variables.iter().map(VariableDFG::from_gql).collect(),
         Some(3)
     ).await;
 }
@@ -398,10 +556,10 @@ pub fn listVariables(
 
 
 #[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
-pub async fn post_add_variable(
+pub async fn post_add_variable<V: VariableType>(
     nvafg: &NavAbilityDFG,
     label: &String,
-    variableType: &String,
+    variableType: &V,
     _tags: Option<Vec<String>>,
     _solvable: Option<i64>,
     _timestamp: Option<chrono::DateTime<Utc>>,
@@ -409,11 +567,7 @@ pub async fn post_add_variable(
     _metadata: Option<String>,
 ) -> Result<Uuid,Box<dyn Error>> {
 
-    let metadata = Some(if _metadata.is_some() {
-        _metadata.unwrap().clone()
-    } else {
-        "e30=".to_string()
-    });
+    let metadata = Some(crate::utils::NodeMetadata::base64_or_empty(&_metadata.unwrap_or_default()));
     let tags = if _tags.is_some() {
         _tags.clone().unwrap()
     } else {
@@ -428,7 +582,7 @@ pub async fn post_add_variable(
     let variables = crate::add_variable::Variables {
         id: nvafg.getId(label).to_string(),
         label: label.to_string(),
-        variable_type: variableType.to_string(),
+        variable_type: variableType.type_str(),
         tags,
         timestamp,
         nstime,
@@ -454,11 +608,11 @@ pub async fn post_add_variable(
 
 
 #[cfg(any(feature = "tokio", feature = "wasm"))]
-pub async fn add_variable_send(
+pub async fn add_variable_send<V: VariableType>(
     send_into: std::sync::mpsc::Sender<Uuid>,
     nvafg: &NavAbilityDFG,
     label: &String,
-    variableType: &String,
+    variableType: &V,
     _tags: Option<Vec<String>>,
     _solvable: Option<i64>,
     _timestamp: Option<chrono::DateTime<Utc>>,
@@ -483,10 +637,10 @@ pub async fn add_variable_send(
 
 
 #[cfg(feature = "tokio")]
-pub fn addVariable(
+pub fn addVariable<V: VariableType>(
     nvafg: &NavAbilityDFG,
     label: &String,
-    variableType: &String,
+    variableType: &V,
     _tags: Option<Vec<String>>,
     _solvable: Option<i64>,
     _timestamp: Option<chrono::DateTime<Utc>>,
@@ -503,4 +657,492 @@ pub fn addVariable(
         _nstime,
         _metadata,
     ));
-}
\ No newline at end of file
+}
+
+
+/// Add an already assembled variable, e.g. from a `LocalDFG`.  Only the variable node itself is
+/// added, PPEs, solver data and blob entries are added separately.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_add_variable_dfg(
+    nvafg: &NavAbilityDFG,
+    variable: &VariableDFG,
+) -> Result<Uuid,Box<dyn Error>> {
+    let ids = post_add_variables(nvafg, std::slice::from_ref(variable)).await?;
+    return Ok(ids[0]);
+}
+
+
+/// Add several variables in a single request, same as `post_add_variable_dfg`, returns their ids
+/// in order.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_add_variables(
+    nvafg: &NavAbilityDFG,
+    variables: &[VariableDFG],
+) -> Result<Vec<Uuid>,Box<dyn Error>> {
+    let mut variables_to_create = Vec::new();
+    for variable in variables {
+        variables_to_create.push(add_variables::VariableCreateInput {
+            id: nvafg.getId(&variable.label).to_string(),
+            label: variable.label.to_string(),
+            variable_type: variable.variableType.to_string(),
+            tags: variable.tags.clone(),
+            timestamp: Some(variable.timestamp.to_string()),
+            nstime: Some(variable.nstime.to_string()),
+            solvable: Some(variable.solvable as i64),
+            metadata: Some(crate::utils::NodeMetadata::base64_or_empty(&variable.metadata)),
+            version: SDK_VERSION.to_string(),
+            fg: None,
+            ppes: None,
+            solver_data: None,
+            blob_entries: None,
+            factors: None,
+        });
+    }
+
+    let request_body = AddVariables::build_query(
+        add_variables::Variables {
+            variables_to_create,
+        }
+    );
+
+    return post_to_nvaapi::<
+        add_variables::Variables,
+        add_variables::ResponseData,
+        Vec<Uuid>
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return s.add_variables.variables.iter().map(|v| {
+                Uuid::parse_str(&v.id).expect("post_add_variables not able to parse uuid from API response")
+            }).collect();
+        },
+        Some(1)
+    ).await;
+}
+
+
+/// Update the type, tags, solvable, timestamps and metadata of an existing variable.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_update_variable(
+    nvafg: &NavAbilityDFG,
+    variable: &VariableDFG,
+) -> Result<Uuid,Box<dyn Error>> {
+    let variables = crate::update_variable::Variables {
+        var_id: nvafg.getId(&variable.label).to_string(),
+        variable_type: Some(variable.variableType.to_string()),
+        tags: Some(variable.tags.clone()),
+        solvable: Some(variable.solvable as i64),
+        timestamp: Some(variable.timestamp.to_string()),
+        nstime: Some(variable.nstime.to_string()),
+        metadata: Some(crate::utils::NodeMetadata::base64_or_empty(&variable.metadata)),
+    };
+
+    let request_body = UpdateVariable::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::update_variable::Variables,
+        crate::update_variable::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.update_variables.variables[0].id).expect("post_update_variable not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
+
+
+/// Delete a variable together with its PPEs, solver data, blob entries and connected factors in a
+/// single request, `nodesDeleted` counts all of them.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+pub async fn post_delete_variable(
+    nvafg: &NavAbilityDFG,
+    label: &str,
+) -> Result<delete_variable::ResponseData, Box<dyn Error>> {
+    let variables = delete_variable::Variables {
+        var_id: nvafg.getId(label).to_string(),
+    };
+
+    let request_body = DeleteVariable::build_query(variables);
+
+    return post_to_nvaapi::<
+        delete_variable::Variables,
+        delete_variable::ResponseData,
+        delete_variable::ResponseData
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| s,
+        Some(1)
+    ).await;
+}
+
+
+// ===================== Solver Data =========================
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_add_solver_data(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    vnd: &PackedVariableNodeData,
+) -> Result<Uuid, Box<dyn Error>> {
+    vnd.validate()?;
+
+    let variables = crate::add_solver_data::Variables {
+        variable_id: nvafg.getId(variableLabel).to_string(),
+        id: nvafg.getId(&format!("{}{}", variableLabel, vnd.solveKey)).to_string(),
+        solve_key: vnd.solveKey.to_string(),
+        vecval: vnd.vecval.clone(),
+        dimval: vnd.dimval as i64,
+        vecbw: vnd.vecbw.clone(),
+        dimbw: vnd.dimbw as i64,
+        bayes_net_out_vert_ids: vnd.BayesNetOutVertIDs.clone(),
+        dim_ids: vnd.dimIDs.iter().map(|x| *x as i64).collect(),
+        dims: vnd.dims as i64,
+        eliminated: vnd.eliminated,
+        bayes_net_vert_id: vnd.BayesNetVertID.to_string(),
+        separator: vnd.separator.clone(),
+        variable_type: vnd.variableType.to_string(),
+        initialized: vnd.initialized,
+        info_per_coord: vnd.infoPerCoord.clone(),
+        ismargin: vnd.ismargin,
+        dontmargin: vnd.dontmargin,
+        solve_in_progress: vnd.solveInProgress as i64,
+        solved_count: vnd.solvedCount as i64,
+        covar: vnd.covar.clone(),
+        version: SDK_VERSION.to_string(),
+    };
+
+    let request_body = AddSolverData::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::add_solver_data::Variables,
+        crate::add_solver_data::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.add_solver_data.solver_data[0].id).expect("post_add_solver_data not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_update_solver_data(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    vnd: &PackedVariableNodeData,
+) -> Result<Uuid, Box<dyn Error>> {
+    vnd.validate()?;
+
+    let variables = crate::update_solver_data::Variables {
+        id: nvafg.getId(&format!("{}{}", variableLabel, vnd.solveKey)).to_string(),
+        vecval: vnd.vecval.clone(),
+        dimval: vnd.dimval as i64,
+        vecbw: vnd.vecbw.clone(),
+        dimbw: vnd.dimbw as i64,
+        bayes_net_out_vert_ids: vnd.BayesNetOutVertIDs.clone(),
+        dim_ids: vnd.dimIDs.iter().map(|x| *x as i64).collect(),
+        dims: vnd.dims as i64,
+        eliminated: vnd.eliminated,
+        bayes_net_vert_id: vnd.BayesNetVertID.to_string(),
+        separator: vnd.separator.clone(),
+        variable_type: vnd.variableType.to_string(),
+        initialized: vnd.initialized,
+        info_per_coord: vnd.infoPerCoord.clone(),
+        ismargin: vnd.ismargin,
+        dontmargin: vnd.dontmargin,
+        solve_in_progress: vnd.solveInProgress as i64,
+        solved_count: vnd.solvedCount as i64,
+        covar: vnd.covar.clone(),
+        version: SDK_VERSION.to_string(),
+    };
+
+    let request_body = UpdateSolverData::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::update_solver_data::Variables,
+        crate::update_solver_data::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.update_solver_data.solver_data[0].id).expect("post_update_solver_data not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_delete_solver_data(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    solveKey: &str,
+) -> Result<delete_solver_data::ResponseData, Box<dyn Error>> {
+    let variables = delete_solver_data::Variables {
+        id: nvafg.getId(&format!("{}{}", variableLabel, solveKey)).to_string(),
+    };
+
+    let request_body = DeleteSolverData::build_query(variables);
+
+    return post_to_nvaapi::<
+        delete_solver_data::Variables,
+        delete_solver_data::ResponseData,
+        delete_solver_data::ResponseData
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| s,
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(feature = "tokio")]
+#[allow(non_snake_case)]
+pub fn addSolverData(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    vnd: &PackedVariableNodeData,
+) -> Result<Uuid, Box<dyn Error>> {
+    return crate::execute(post_add_solver_data(
+        nvafg,
+        variableLabel,
+        vnd,
+    ));
+}
+
+
+#[cfg(feature = "tokio")]
+#[allow(non_snake_case)]
+pub fn updateSolverData(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    vnd: &PackedVariableNodeData,
+) -> Result<Uuid, Box<dyn Error>> {
+    return crate::execute(post_update_solver_data(
+        nvafg,
+        variableLabel,
+        vnd,
+    ));
+}
+
+
+#[cfg(feature = "tokio")]
+#[allow(non_snake_case)]
+pub fn deleteSolverData(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    solveKey: &str,
+) -> Result<delete_solver_data::ResponseData, Box<dyn Error>> {
+    return crate::execute(post_delete_solver_data(
+        nvafg,
+        variableLabel,
+        solveKey,
+    ));
+}
+
+
+// ===================== PPEs =========================
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_add_ppe(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    ppe: &MeanMaxPPE,
+) -> Result<Uuid, Box<dyn Error>> {
+    let variables = crate::add_ppe::Variables {
+        variable_id: nvafg.getId(variableLabel).to_string(),
+        id: nvafg.getId(&format!("{}{}", variableLabel, ppe.solveKey)).to_string(),
+        solve_key: ppe.solveKey.to_string(),
+        suggested: ppe.suggested.clone(),
+        max: ppe.max.clone(),
+        mean: ppe.mean.clone(),
+        type_: ppe._type.to_string(),
+        version: ppe._version.to_string(),
+    };
+
+    let request_body = AddPPE::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::add_ppe::Variables,
+        crate::add_ppe::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.add_ppes.ppes[0].id).expect("post_add_ppe not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_update_ppe(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    ppe: &MeanMaxPPE,
+) -> Result<Uuid, Box<dyn Error>> {
+    let variables = crate::update_ppe::Variables {
+        id: nvafg.getId(&format!("{}{}", variableLabel, ppe.solveKey)).to_string(),
+        suggested: ppe.suggested.clone(),
+        max: ppe.max.clone(),
+        mean: ppe.mean.clone(),
+        type_: ppe._type.to_string(),
+        version: ppe._version.to_string(),
+    };
+
+    let request_body = UpdatePPE::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::update_ppe::Variables,
+        crate::update_ppe::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.update_ppes.ppes[0].id).expect("post_update_ppe not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_delete_ppe(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    solveKey: &str,
+) -> Result<delete_ppe::ResponseData, Box<dyn Error>> {
+    let variables = delete_ppe::Variables {
+        id: nvafg.getId(&format!("{}{}", variableLabel, solveKey)).to_string(),
+    };
+
+    let request_body = DeletePPE::build_query(variables);
+
+    return post_to_nvaapi::<
+        delete_ppe::Variables,
+        delete_ppe::ResponseData,
+        delete_ppe::ResponseData
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| s,
+        Some(1)
+    ).await;
+}
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_get_ppe(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    solveKey: &str,
+) -> Result<Option<MeanMaxPPE>, Box<dyn Error>> {
+    let variables = crate::get_ppe::Variables {
+        ppe_id: nvafg.getId(&format!("{}{}", variableLabel, solveKey)).to_string(),
+    };
+
+    let request_body = GetPPE::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::get_ppe::Variables,
+        crate::get_ppe::ResponseData,
+        Option<MeanMaxPPE>
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| s.ppes.first().map(MeanMaxPPE::from),
+        Some(3)
+    ).await;
+}
+
+
+/// Solve keys of the PPEs on a variable, `None` if there is no such variable.
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_list_ppes(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+    let variables = crate::list_ppe_keys::Variables {
+        var_id: nvafg.getId(variableLabel).to_string(),
+    };
+
+    let request_body = ListPPEKeys::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::list_ppe_keys::Variables,
+        crate::list_ppe_keys::ResponseData,
+        Option<Vec<String>>
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| s.variables.first().map(|v| v.ppes.iter().map(|p| p.solve_key.clone()).collect()),
+        Some(3)
+    ).await;
+}
+
+
+// ===================== Blob Entries =========================
+
+
+#[cfg(any(feature = "tokio", feature = "wasm", feature = "blocking"))]
+#[allow(non_snake_case)]
+pub async fn post_add_variable_entry(
+    nvafg: &NavAbilityDFG,
+    variableLabel: &str,
+    entry: &BlobEntry,
+) -> Result<Uuid, Box<dyn Error>> {
+    let metadata = crate::utils::NodeMetadata::base64_or_empty(&entry.metadata);
+
+    let variables = crate::add_blob_entry_variable::Variables {
+        variable_id: nvafg.getId(variableLabel).to_string(),
+        entry_id: nvafg.getId(&format!("{}{}", variableLabel, entry.label)).to_string(),
+        entry_label: entry.label.to_string(),
+        blob_id: entry.blobId.to_string(),
+        blobstore: Some(entry.blobstore.to_string()),
+        origin: Some(entry.origin.to_string()),
+        mime_type: Some(entry.mimeType.to_string()),
+        metadata,
+        description: Some(entry.description.to_string()),
+        hash: entry.hash.to_string(),
+        size: entry.size.map(|sz| format!("{}", sz)),
+        timestamp: Some(entry.timestamp.to_string()),
+    };
+
+    let request_body = AddBlobEntryVariable::build_query(variables);
+
+    return post_to_nvaapi::<
+        crate::add_blob_entry_variable::Variables,
+        crate::add_blob_entry_variable::ResponseData,
+        Uuid
+    >(
+        &nvafg.client,
+        request_body, 
+        |s| {
+            return Uuid::parse_str(&s.add_blob_entries.blob_entries[0].id).expect("post_add_variable_entry not able to parse uuid from API response");
+        },
+        Some(1)
+    ).await;
+}
@@ -0,0 +1,221 @@
+
+use std::{
+  collections::BTreeMap,
+  error::Error,
+};
+
+use crate::{
+  FactorGraphStore,
+  FactorType,
+  PlotOptions,
+};
+
+
+/// What is drawn of a variable or factor.
+struct PlotNode {
+  label: String,
+  is_variable: bool,
+  /// variable type or factor type, e.g. "RoME.Pose2"
+  kind: String,
+  tags: Vec<String>,
+  solvable: i64,
+  /// connected variables of a factor
+  variables: Vec<String>,
+}
+
+impl PlotNode {
+  fn cluster(
+    &self,
+    options: &PlotOptions,
+  ) -> Option<String> {
+    let prefix = options.cluster_by.as_ref()?;
+    return self.tags.iter().find(|t| t.starts_with(prefix.as_str())).cloned();
+  }
+}
+
+
+const PAGE_SIZE: usize = 100;
+
+/// Snapshot of the nodes to draw, variables first, read in pages with `getVariables` and
+/// `getFactors`.
+async fn plot_nodes<G: FactorGraphStore>(
+  dfg: &G,
+) -> Result<Vec<PlotNode>, Box<dyn Error>> {
+  let mut nodes = Vec::new();
+  for page in dfg.listVariables().await?.chunks(PAGE_SIZE) {
+    for v in dfg.getVariables(page).await? {
+      nodes.push(PlotNode {
+        label: v.label,
+        is_variable: true,
+        kind: v.variableType,
+        tags: v.tags,
+        solvable: v.solvable as i64,
+        variables: Vec::new(),
+      });
+    }
+  }
+  for page in dfg.lsf().await?.chunks(PAGE_SIZE) {
+    for f in dfg.getFactors(page).await? {
+      nodes.push(PlotNode {
+        label: f.label,
+        is_variable: false,
+        kind: f.fnctype.type_str(),
+        tags: f.tags,
+        solvable: f.solvable.unwrap_or(1),
+        variables: f.variableOrderSymbols_,
+      });
+    }
+  }
+  return Ok(nodes);
+}
+
+
+fn dot_quote(
+  s: &str,
+) -> String {
+  return format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+}
+
+fn dot_node(
+  node: &PlotNode,
+) -> String {
+  let mut style = vec!["filled"];
+  if node.solvable == 0 {
+    style.push("dashed");
+  }
+  return format!(
+    "{} [shape={}, style={}, fillcolor={}, class={}, tooltip={}];",
+    dot_quote(&node.label),
+    if node.is_variable { "ellipse" } else { "box" },
+    dot_quote(&style.join(",")),
+    if node.solvable == 0 { "gray90" } else if node.is_variable { "lightblue" } else { "white" },
+    dot_quote(&node.tags.join(" ")),
+    dot_quote(&format!("{} [{}] solvable={}", &node.kind, node.tags.join(", "), node.solvable)),
+  );
+}
+
+
+/// Render a graph as GraphViz DOT, variables as ellipses and factors as boxes.  Unsolvable nodes
+/// (`solvable == 0`) are dashed and grayed out, and the tags of each node are its `class`, which
+/// SVG output keeps for styling.  Nodes can be grouped into clusters by tag, see `PlotOptions`.
+/// Works with a `LocalDFG` snapshot or directly with a `NavAbilityDFG`, e.g.
+/// `dot -Tsvg graph.dot -o graph.svg`.
+pub async fn to_dot<G: FactorGraphStore>(
+  dfg: &G,
+  options: &PlotOptions,
+) -> Result<String, Box<dyn Error>> {
+  let nodes = plot_nodes(dfg).await?;
+
+  let mut clusters: BTreeMap<Option<String>, Vec<&PlotNode>> = BTreeMap::new();
+  for node in &nodes {
+    clusters.entry(node.cluster(options)).or_default().push(node);
+  }
+
+  let mut out = String::from("graph factorgraph {\n  node [fontsize=10];\n");
+  for (cluster, members) in &clusters {
+    let indent = if cluster.is_some() { "    " } else { "  " };
+    if let Some(name) = cluster {
+      out.push_str(&format!("  subgraph {} {{\n    label={};\n", dot_quote(&format!("cluster_{}", name)), dot_quote(name)));
+    }
+    for node in members {
+      out.push_str(indent);
+      out.push_str(&dot_node(node));
+      out.push('\n');
+    }
+    if cluster.is_some() {
+      out.push_str("  }\n");
+    }
+  }
+  for node in &nodes {
+    for vl in &node.variables {
+      out.push_str(&format!("  {} -- {};\n", dot_quote(&node.label), dot_quote(vl)));
+    }
+  }
+  out.push_str("}\n");
+  return Ok(out);
+}
+
+
+/// Render a graph as Cytoscape.js JSON (`{"elements": {"nodes": [..], "edges": [..]}}`) for web
+/// viewers.  Node data holds the label, type, tags and solvable, and the classes `variable` or
+/// `factor`, `unsolvable` and the tags can be used in a stylesheet.  Clusters, see `PlotOptions`,
+/// become compound parent nodes.
+pub async fn to_cytoscape_json<G: FactorGraphStore>(
+  dfg: &G,
+  options: &PlotOptions,
+) -> Result<serde_json::Value, Box<dyn Error>> {
+  let nodes = plot_nodes(dfg).await?;
+
+  let mut elements = Vec::new();
+  let mut clusters = Vec::new();
+  let mut edges = Vec::new();
+  for node in &nodes {
+    let cluster = node.cluster(options);
+    if let Some(c) = &cluster {
+      if !clusters.contains(c) {
+        clusters.push(c.clone());
+      }
+    }
+    let mut classes = vec![if node.is_variable { "variable" } else { "factor" }.to_owned()];
+    if node.solvable == 0 {
+      classes.push("unsolvable".to_owned());
+    }
+    classes.extend(node.tags.iter().cloned());
+    let mut data = serde_json::json!({
+      "id": &node.label,
+      "label": &node.label,
+      "type": &node.kind,
+      "tags": &node.tags,
+      "solvable": node.solvable,
+    });
+    if let Some(c) = cluster {
+      data["parent"] = serde_json::Value::String(format!("cluster_{}", c));
+    }
+    elements.push(serde_json::json!({ "data": data, "classes": classes.join(" ") }));
+    for vl in &node.variables {
+      edges.push(serde_json::json!({
+        // labels are [a-zA-Z0-9_], so `|` can not make two edge ids the same
+        "data": { "id": format!("{}|{}", &node.label, vl), "source": &node.label, "target": vl },
+      }));
+    }
+  }
+  for c in clusters {
+    elements.push(serde_json::json!({
+      "data": { "id": format!("cluster_{}", &c), "label": c },
+      "classes": "cluster",
+    }));
+  }
+  return Ok(serde_json::json!({ "elements": { "nodes": elements, "edges": edges } }));
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{LocalDFG, Uuid, Pose2, Pose2Pose2, FullNormal, FactorDFG};
+
+  #[test]
+  fn test_to_dot() {
+    let mut dfg = LocalDFG::new(Uuid::nil(), "plot");
+    dfg.add_variable("x0", &Pose2, Some(vec!["SESSION_A".to_owned()]), None, None, None, None).unwrap();
+    dfg.add_variable("x1", &Pose2, Some(vec!["SESSION_B".to_owned()]), Some(0), None, None, None).unwrap();
+    let z = FullNormal::new(vec![1.0, 0.0, 0.0], vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap();
+    let mut f = FactorDFG::new(vec!["x0".to_owned(), "x1".to_owned()], Pose2Pose2::new(z), vec![], None, None).unwrap();
+    f.label = "x0x1f1".to_owned();
+    f.solvable = Some(1);
+    dfg.add_factor(f).unwrap();
+
+    let options = PlotOptions { cluster_by: Some("SESSION_".to_owned()) };
+    let dot = crate::block_on_ready(to_dot(&dfg, &options)).unwrap();
+    let lines: Vec<&str> = dot.lines().map(|l| l.trim()).collect();
+    assert_eq!(lines.first(), Some(&"graph factorgraph {"));
+    assert_eq!(lines.last(), Some(&"}"));
+    assert!(lines.contains(&"subgraph \"cluster_SESSION_A\" {"), "{}", dot);
+    assert!(lines.contains(&"subgraph \"cluster_SESSION_B\" {"), "{}", dot);
+    assert!(lines.iter().any(|l| l.starts_with("\"x0\" [shape=ellipse, style=\"filled\", fillcolor=lightblue,")), "{}", dot);
+    assert!(lines.iter().any(|l| l.starts_with("\"x1\" [shape=ellipse, style=\"filled,dashed\", fillcolor=gray90,")), "{}", dot);
+    assert!(lines.iter().any(|l| l.starts_with("\"x0x1f1\" [shape=box, style=\"filled\", fillcolor=white,")), "{}", dot);
+    assert!(lines.contains(&"\"x0x1f1\" -- \"x0\";"), "{}", dot);
+    assert!(lines.contains(&"\"x0x1f1\" -- \"x1\";"), "{}", dot);
+  }
+}
@@ -3,4 +3,9 @@ pub mod fileuploader;
 pub use fileuploader::*;
 
 pub mod common;
-pub use common::*;
\ No newline at end of file
+pub use common::*;
+pub mod linalg;
+pub mod geometry;
+pub use geometry::{SE2, SE3};
+pub mod metadata;
+pub use metadata::*;
@@ -70,6 +70,18 @@ f: F
   // std::thread::spawn(move || futures::executor::block_on(f));
 }
 
+/// Run a future that never waits on IO, e.g. the `LocalDFG` methods, in tests without a runtime.
+#[cfg(test)]
+pub(crate) fn block_on_ready<R, F: Future<Output = R>>(
+  f: F,
+) -> R {
+  let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+  return match std::pin::pin!(f).poll(&mut cx) {
+    std::task::Poll::Ready(out) => out,
+    std::task::Poll::Pending => panic!("future is waiting on IO"),
+  };
+}
+
 
 
 /// Returns the type name of a given value.
@@ -179,6 +191,18 @@ impl fmt::Display for GQLResponseErrors {
 
 impl Error for GQLResponseErrors {}
 
+impl GQLResponseErrors {
+  /// True if the API rejected a create because a node with the same id already exists, i.e. the
+  /// Neo4j uniqueness constraint error, by its error code or its `already exists with label` message.
+  pub fn is_duplicate(&self) -> bool {
+    return self.details.iter().any(|e| {
+      let code = e.extensions.as_ref().and_then(|ext| ext.get("code")).and_then(|c| c.as_str());
+      code == Some("Neo.ClientError.Schema.ConstraintValidationFailed")
+        || e.message.contains("already exists with label")
+    });
+  }
+}
+
 #[derive(Debug)]
 pub struct GQLRequestError {
   pub details: String,
@@ -192,6 +216,58 @@ impl fmt::Display for GQLRequestError {
 
 impl Error for GQLRequestError {}
 
+#[derive(Debug)]
+pub struct ValidationError {
+  pub details: String,
+}
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "NvaSDK, validation error {}", self.details)
+  }
+}
+
+impl Error for ValidationError {}
+
+#[derive(Debug)]
+pub struct ConflictError {
+  pub details: String,
+}
+
+impl fmt::Display for ConflictError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "NvaSDK, conflict error {}", self.details)
+  }
+}
+
+impl Error for ConflictError {}
+
+#[derive(Debug)]
+pub struct NotFoundError {
+  pub details: String,
+}
+
+impl fmt::Display for NotFoundError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "NvaSDK, not found error {}", self.details)
+  }
+}
+
+impl Error for NotFoundError {}
+
+#[derive(Debug)]
+pub struct ImportError {
+  pub details: String,
+}
+
+impl fmt::Display for ImportError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "NvaSDK, import error {}", self.details)
+  }
+}
+
+impl Error for ImportError {}
+
 
 /// Checks the ResponseData: F of a GraphQL query and applies a user specified modifier callback.
 ///
@@ -398,4 +474,44 @@ T
 //             }
 //         }
 //     }
-// }
\ No newline at end of file
+// }
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn response_errors(
+    body: &str
+  ) -> GQLResponseErrors {
+    let res: graphql_client::Response<serde_json::Value> = serde_json::from_str(body).unwrap();
+    return GQLResponseErrors {
+      details: res.errors.unwrap(),
+    };
+  }
+
+  #[test]
+  fn test_is_duplicate() {
+    let dup = response_errors(r#"{
+      "data": null,
+      "errors": [{
+        "message": "Node(1742) already exists with label `Factor` and property `id` = 'b6c3f1f4-51a5-5c2a-9d2e-6f0c1a7e4d21'",
+        "locations": [{"line": 2, "column": 3}],
+        "path": ["addFactors"],
+        "extensions": {"code": "Neo.ClientError.Schema.ConstraintValidationFailed"}
+      }]
+    }"#);
+    assert!(dup.is_duplicate());
+
+    // a missing required property also fails a Neo4j constraint but is not a duplicate
+    let other = response_errors(r#"{
+      "data": null,
+      "errors": [{
+        "message": "Constraint validation failed",
+        "path": ["addFactors"],
+        "extensions": {"code": "INTERNAL_SERVER_ERROR"}
+      }]
+    }"#);
+    assert!(!other.is_duplicate());
+  }
+}
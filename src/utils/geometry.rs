@@ -0,0 +1,603 @@
+
+//! SE(2) and SE(3) pose helpers for building odometry measurements.
+//!
+//! Tangent vectors and covariances are ordered translation first then rotation, i.e.
+//! `[x, y, θ]` for SE(2) and `[x, y, z, ωx, ωy, ωz]` for SE(3), and perturbations are
+//! applied on the right, `p ⊕ δ = p * exp(δ)`.  Matrices are row-major as in `linalg`.
+
+use std::f64::consts::PI;
+
+use crate::utils::linalg;
+
+
+// below this angle the closed forms lose precision and the series expansions are used
+const SMALL_ANGLE: f64 = 1e-5;
+
+/// Wrap an angle to [-π, π).
+pub fn wrap_angle(
+  theta: f64
+) -> f64 {
+  let t = (theta + PI).rem_euclid(2.0 * PI) - PI;
+  return t;
+}
+
+fn identity(
+  n: usize
+) -> Vec<f64> {
+  let mut a = vec![0.0; n*n];
+  for i in 0..n {
+    a[i*n + i] = 1.0;
+  }
+  return a;
+}
+
+/// Sum of two covariances propagated through the Jacobians `ja` and `jb`.
+fn propagate(
+  ja: &[f64],
+  cov_a: &[f64],
+  jb: &[f64],
+  cov_b: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  let ca = linalg::congruence(ja, cov_a, n);
+  let cb = linalg::congruence(jb, cov_b, n);
+  let mut c = vec![0.0; n*n];
+  for i in 0..n {
+    for j in 0..n {
+      // symmetrize away floating point round-off
+      c[i*n + j] = 0.5 * (ca[i*n + j] + ca[j*n + i] + cb[i*n + j] + cb[j*n + i]);
+    }
+  }
+  return c;
+}
+
+
+// ========================= SE(2) =========================
+
+
+/// Planar pose `(x, y, θ)`, same coordinates as a `Pose2` variable.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SE2 {
+  pub x: f64,
+  pub y: f64,
+  pub theta: f64,
+}
+
+impl SE2 {
+  pub fn new(
+    x: f64,
+    y: f64,
+    theta: f64,
+  ) -> Self {
+    return Self { x, y, theta: wrap_angle(theta) };
+  }
+
+  pub fn identity() -> Self {
+    return Self::default();
+  }
+
+  /// Returns `self * other`, i.e. `other` expressed in the frame of `self`.
+  pub fn compose(
+    &self,
+    other: &SE2,
+  ) -> SE2 {
+    let (s, c) = self.theta.sin_cos();
+    return SE2::new(
+      self.x + c * other.x - s * other.y,
+      self.y + s * other.x + c * other.y,
+      self.theta + other.theta,
+    );
+  }
+
+  pub fn inverse(&self) -> SE2 {
+    let (s, c) = self.theta.sin_cos();
+    return SE2::new(
+      -c * self.x - s * self.y,
+      s * self.x - c * self.y,
+      -self.theta,
+    );
+  }
+
+  /// Relative pose `self⁻¹ * other`, i.e. `other` as seen from `self`.
+  pub fn between(
+    &self,
+    other: &SE2,
+  ) -> SE2 {
+    return self.inverse().compose(other);
+  }
+
+  /// Group exponential of the tangent vector `[vx, vy, ω]`.
+  pub fn exp(
+    xi: &[f64; 3]
+  ) -> SE2 {
+    let [vx, vy, w] = *xi;
+    if w.abs() < SMALL_ANGLE {
+      return SE2::new(vx, vy, w);
+    }
+    let a = w.sin() / w;
+    let b = (1.0 - w.cos()) / w;
+    return SE2::new(a * vx - b * vy, b * vx + a * vy, w);
+  }
+
+  /// Group logarithm, inverse of `exp`.
+  pub fn log(&self) -> [f64; 3] {
+    let w = self.theta;
+    if w.abs() < SMALL_ANGLE {
+      return [self.x, self.y, w];
+    }
+    let a = w.sin() / w;
+    let b = (1.0 - w.cos()) / w;
+    let det = a * a + b * b;
+    return [
+      (a * self.x + b * self.y) / det,
+      (-b * self.x + a * self.y) / det,
+      w,
+    ];
+  }
+
+  /// Adjoint matrix (3x3, row-major) acting on tangent vectors `[vx, vy, ω]`.
+  pub fn adjoint(&self) -> Vec<f64> {
+    let (s, c) = self.theta.sin_cos();
+    return vec![
+      c,   -s,  self.y,
+      s,   c,   -self.x,
+      0.0, 0.0, 1.0,
+    ];
+  }
+
+  /// Relative pose `self⁻¹ * other` together with its covariance, given independent covariances of
+  /// `self` and `other` (3x3, row-major).
+  pub fn between_with_cov(
+    &self,
+    cov_self: &[f64],
+    other: &SE2,
+    cov_other: &[f64],
+  ) -> (SE2, Vec<f64>) {
+    let d = self.between(other);
+    let ja: Vec<f64> = d.inverse().adjoint().iter().map(|v| -v).collect();
+    let cov = propagate(&ja, cov_self, &identity(3), cov_other, 3);
+    return (d, cov);
+  }
+
+  /// Measurement vector `[x, y, θ]` as used by `Pose2` factors.
+  pub fn to_vec(&self) -> Vec<f64> {
+    return vec![self.x, self.y, self.theta];
+  }
+}
+
+
+// ========================= SE(3) =========================
+
+
+fn quat_mul(
+  a: &[f64; 4],
+  b: &[f64; 4],
+) -> [f64; 4] {
+  let [aw, ax, ay, az] = *a;
+  let [bw, bx, by, bz] = *b;
+  return [
+    aw*bw - ax*bx - ay*by - az*bz,
+    aw*bx + ax*bw + ay*bz - az*by,
+    aw*by - ax*bz + ay*bw + az*bx,
+    aw*bz + ax*by - ay*bx + az*bw,
+  ];
+}
+
+fn quat_normalize(
+  q: &[f64; 4]
+) -> [f64; 4] {
+  let n = q.iter().map(|v| v*v).sum::<f64>().sqrt();
+  return [q[0]/n, q[1]/n, q[2]/n, q[3]/n];
+}
+
+fn norm3(
+  a: &[f64; 3]
+) -> f64 {
+  return (a[0]*a[0] + a[1]*a[1] + a[2]*a[2]).sqrt();
+}
+
+/// Rotation vector (axis times angle) to unit quaternion `[w, x, y, z]`.
+pub fn so3_exp(
+  w: &[f64; 3]
+) -> [f64; 4] {
+  let theta = norm3(w);
+  if theta < SMALL_ANGLE {
+    return quat_normalize(&[1.0, w[0]/2.0, w[1]/2.0, w[2]/2.0]);
+  }
+  let k = (theta / 2.0).sin() / theta;
+  return [(theta / 2.0).cos(), k * w[0], k * w[1], k * w[2]];
+}
+
+/// Unit quaternion `[w, x, y, z]` to rotation vector with angle in [0, π].
+pub fn so3_log(
+  q: &[f64; 4]
+) -> [f64; 3] {
+  let q = if q[0] < 0.0 { [-q[0], -q[1], -q[2], -q[3]] } else { *q };
+  let v = [q[1], q[2], q[3]];
+  let n = norm3(&v);
+  if n < SMALL_ANGLE {
+    return [2.0 * v[0] / q[0], 2.0 * v[1] / q[0], 2.0 * v[2] / q[0]];
+  }
+  let k = 2.0 * n.atan2(q[0]) / n;
+  return [k * v[0], k * v[1], k * v[2]];
+}
+
+/// Roll, pitch, yaw (rotation `Rz(yaw) * Ry(pitch) * Rx(roll)`) to unit quaternion `[w, x, y, z]`.
+pub fn euler_to_quat(
+  rpy: &[f64; 3]
+) -> [f64; 4] {
+  let (sr, cr) = (rpy[0] / 2.0).sin_cos();
+  let (sp, cp) = (rpy[1] / 2.0).sin_cos();
+  let (sy, cy) = (rpy[2] / 2.0).sin_cos();
+  return [
+    cr*cp*cy + sr*sp*sy,
+    sr*cp*cy - cr*sp*sy,
+    cr*sp*cy + sr*cp*sy,
+    cr*cp*sy - sr*sp*cy,
+  ];
+}
+
+/// Unit quaternion `[w, x, y, z]` to roll, pitch, yaw, inverse of `euler_to_quat`.
+pub fn quat_to_euler(
+  q: &[f64; 4]
+) -> [f64; 3] {
+  let [w, x, y, z] = *q;
+  let roll = (2.0*(w*x + y*z)).atan2(1.0 - 2.0*(x*x + y*y));
+  let pitch = (2.0*(w*y - z*x)).clamp(-1.0, 1.0).asin();
+  let yaw = (2.0*(w*z + x*y)).atan2(1.0 - 2.0*(y*y + z*z));
+  return [roll, pitch, yaw];
+}
+
+/// Unit quaternion `[w, x, y, z]` to 3x3 row-major rotation matrix.
+pub fn quat_to_rotmat(
+  q: &[f64; 4]
+) -> [f64; 9] {
+  let [w, x, y, z] = *q;
+  return [
+    1.0 - 2.0*(y*y + z*z), 2.0*(x*y - w*z),       2.0*(x*z + w*y),
+    2.0*(x*y + w*z),       1.0 - 2.0*(x*x + z*z), 2.0*(y*z - w*x),
+    2.0*(x*z - w*y),       2.0*(y*z + w*x),       1.0 - 2.0*(x*x + y*y),
+  ];
+}
+
+fn skew(
+  v: &[f64; 3]
+) -> [f64; 9] {
+  return [
+    0.0,   -v[2], v[1],
+    v[2],  0.0,   -v[0],
+    -v[1], v[0],  0.0,
+  ];
+}
+
+fn mat3_vec(
+  m: &[f64],
+  v: &[f64; 3],
+) -> [f64; 3] {
+  return [
+    m[0]*v[0] + m[1]*v[1] + m[2]*v[2],
+    m[3]*v[0] + m[4]*v[1] + m[5]*v[2],
+    m[6]*v[0] + m[7]*v[1] + m[8]*v[2],
+  ];
+}
+
+/// `I + a*K + b*K²` for the 3x3 skew matrix `K`.
+fn so3_series(
+  k: &[f64; 9],
+  a: f64,
+  b: f64,
+) -> Vec<f64> {
+  let k2 = linalg::matmul(k, k, 3);
+  let mut m = identity(3);
+  for i in 0..9 {
+    m[i] += a * k[i] + b * k2[i];
+  }
+  return m;
+}
+
+
+/// Pose with translation `t` and unit quaternion rotation `q = [w, x, y, z]`, same
+/// coordinates as a `Pose3` variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SE3 {
+  pub t: [f64; 3],
+  pub q: [f64; 4],
+}
+
+impl Default for SE3 {
+  fn default() -> Self {
+    return Self { t: [0.0; 3], q: [1.0, 0.0, 0.0, 0.0] };
+  }
+}
+
+impl SE3 {
+  /// The quaternion `q = [w, x, y, z]` is normalized.
+  pub fn new(
+    t: [f64; 3],
+    q: [f64; 4],
+  ) -> Self {
+    return Self { t, q: quat_normalize(&q) };
+  }
+
+  /// Pose from translation and rotation vector (axis times angle).
+  pub fn from_rotvec(
+    t: [f64; 3],
+    w: [f64; 3],
+  ) -> Self {
+    return Self { t, q: so3_exp(&w) };
+  }
+
+  pub fn identity() -> Self {
+    return Self::default();
+  }
+
+  /// Row-major 3x3 rotation matrix.
+  pub fn rotation(&self) -> [f64; 9] {
+    return quat_to_rotmat(&self.q);
+  }
+
+  /// Returns `self * other`, i.e. `other` expressed in the frame of `self`.
+  pub fn compose(
+    &self,
+    other: &SE3,
+  ) -> SE3 {
+    let rt = mat3_vec(&self.rotation(), &other.t);
+    return SE3::new(
+      [self.t[0] + rt[0], self.t[1] + rt[1], self.t[2] + rt[2]],
+      quat_mul(&self.q, &other.q),
+    );
+  }
+
+  pub fn inverse(&self) -> SE3 {
+    let rt = linalg::transpose(&self.rotation(), 3);
+    let t = mat3_vec(&rt, &self.t);
+    return SE3::new(
+      [-t[0], -t[1], -t[2]],
+      [self.q[0], -self.q[1], -self.q[2], -self.q[3]],
+    );
+  }
+
+  /// Relative pose `self⁻¹ * other`, i.e. `other` as seen from `self`.
+  pub fn between(
+    &self,
+    other: &SE3,
+  ) -> SE3 {
+    return self.inverse().compose(other);
+  }
+
+  /// Group exponential of the tangent vector `[ρx, ρy, ρz, ωx, ωy, ωz]`.
+  pub fn exp(
+    xi: &[f64; 6]
+  ) -> SE3 {
+    let rho = [xi[0], xi[1], xi[2]];
+    let w = [xi[3], xi[4], xi[5]];
+    let theta = norm3(&w);
+    let v = if theta < SMALL_ANGLE {
+      so3_series(&skew(&w), 0.5, 1.0/6.0)
+    } else {
+      let t2 = theta * theta;
+      so3_series(&skew(&w), (1.0 - theta.cos()) / t2, (theta - theta.sin()) / (t2 * theta))
+    };
+    return SE3 { t: mat3_vec(&v, &rho), q: so3_exp(&w) };
+  }
+
+  /// Group logarithm, inverse of `exp`.
+  pub fn log(&self) -> [f64; 6] {
+    let w = so3_log(&self.q);
+    let theta = norm3(&w);
+    let vinv = if theta < SMALL_ANGLE {
+      so3_series(&skew(&w), -0.5, 1.0/12.0)
+    } else {
+      let b = (1.0 - theta * theta.sin() / (2.0 * (1.0 - theta.cos()))) / (theta * theta);
+      so3_series(&skew(&w), -0.5, b)
+    };
+    let rho = mat3_vec(&vinv, &self.t);
+    return [rho[0], rho[1], rho[2], w[0], w[1], w[2]];
+  }
+
+  /// Adjoint matrix (6x6, row-major) acting on tangent vectors `[ρ, ω]`.
+  pub fn adjoint(&self) -> Vec<f64> {
+    let r = self.rotation();
+    let tr = linalg::matmul(&skew(&self.t), &r, 3);
+    let mut ad = vec![0.0; 36];
+    for i in 0..3 {
+      for j in 0..3 {
+        ad[i*6 + j] = r[i*3 + j];
+        ad[i*6 + j + 3] = tr[i*3 + j];
+        ad[(i + 3)*6 + j + 3] = r[i*3 + j];
+      }
+    }
+    return ad;
+  }
+
+  /// Relative pose `self⁻¹ * other` together with its covariance, given independent covariances of
+  /// `self` and `other` (6x6, row-major).
+  pub fn between_with_cov(
+    &self,
+    cov_self: &[f64],
+    other: &SE3,
+    cov_other: &[f64],
+  ) -> (SE3, Vec<f64>) {
+    let d = self.between(other);
+    let ja: Vec<f64> = d.inverse().adjoint().iter().map(|v| -v).collect();
+    let cov = propagate(&ja, cov_self, &identity(6), cov_other, 6);
+    return (d, cov);
+  }
+
+  /// Measurement vector `[x, y, z, ωx, ωy, ωz]`, translation and rotation vector, as used by `Pose3` factors.
+  pub fn to_vec(&self) -> Vec<f64> {
+    let w = so3_log(&self.q);
+    return vec![self.t[0], self.t[1], self.t[2], w[0], w[1], w[2]];
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(
+    a: &[f64],
+    b: &[f64],
+    tol: f64,
+  ) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+      assert!((x - y).abs() <= tol * (1.0 + x.abs().max(y.abs())), "entry {}: {} != {}\n{:?}\n{:?}", i, x, y, a, b);
+    }
+  }
+
+  fn se2_close(
+    a: &SE2,
+    b: &SE2,
+  ) {
+    assert_close(&[a.x, a.y], &[b.x, b.y], 1e-9);
+    assert!(wrap_angle(a.theta - b.theta).abs() < 1e-9, "{:?} != {:?}", a, b);
+  }
+
+  fn se3_close(
+    a: &SE3,
+    b: &SE3,
+  ) {
+    assert_close(&a.t, &b.t, 1e-9);
+    assert_close(&a.rotation(), &b.rotation(), 1e-9);
+  }
+
+  /// Covariance of `a.between(b)` by central differences of `log(d⁻¹ * between(a ⊕ δa, b ⊕ δb))`.
+  fn numeric_between_cov<const N: usize>(
+    between: impl Fn(&[f64; N], &[f64; N]) -> [f64; N],
+    cov_a: &[f64],
+    cov_b: &[f64],
+  ) -> Vec<f64> {
+    let h = 1e-6;
+    let mut ja = vec![0.0; N*N];
+    let mut jb = vec![0.0; N*N];
+    for k in 0..N {
+      let mut dp = [0.0; N];
+      let mut dm = [0.0; N];
+      dp[k] = h;
+      dm[k] = -h;
+      let zero = [0.0; N];
+      let (fa, fa_) = (between(&dp, &zero), between(&dm, &zero));
+      let (fb, fb_) = (between(&zero, &dp), between(&zero, &dm));
+      for i in 0..N {
+        ja[i*N + k] = (fa[i] - fa_[i]) / (2.0 * h);
+        jb[i*N + k] = (fb[i] - fb_[i]) / (2.0 * h);
+      }
+    }
+    let ca = linalg::congruence(&ja, cov_a, N);
+    let cb = linalg::congruence(&jb, cov_b, N);
+    return ca.iter().zip(&cb).map(|(a, b)| a + b).collect();
+  }
+
+  #[test]
+  fn test_se2_exp_log() {
+    for p in [SE2::new(1.0, -2.0, 0.3), SE2::new(-0.5, 0.25, -3.0), SE2::new(2.0, 1.0, 1e-7)] {
+      se2_close(&SE2::exp(&p.log()), &p);
+    }
+    assert_close(&SE2::new(0.0, 0.0, 0.0).log(), &[0.0, 0.0, 0.0], 1e-12);
+  }
+
+  #[test]
+  fn test_se3_exp_log() {
+    for p in [
+      SE3::from_rotvec([1.0, -2.0, 0.5], [0.1, -0.2, 0.3]),
+      SE3::from_rotvec([0.0, 3.0, -1.0], [2.0, 1.0, -1.5]),
+      SE3::from_rotvec([0.5, 0.5, 0.5], [1e-8, 0.0, -1e-8]),
+    ] {
+      se3_close(&SE3::exp(&p.log()), &p);
+    }
+  }
+
+  #[test]
+  fn test_between() {
+    // b is one unit ahead of a, which faces along +y, and turned a further quarter turn
+    let a = SE2::new(1.0, 2.0, PI / 2.0);
+    let b = SE2::new(1.0, 3.0, PI);
+    se2_close(&a.between(&b), &SE2::new(1.0, 0.0, PI / 2.0));
+    se2_close(&a.compose(&a.between(&b)), &b);
+
+    let rz = [0.0, 0.0, PI / 2.0];
+    let a = SE3::from_rotvec([1.0, 0.0, 0.0], rz);
+    let b = SE3::from_rotvec([1.0, 1.0, 0.0], rz);
+    se3_close(&a.between(&b), &SE3::new([1.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0]));
+    se3_close(&a.compose(&a.between(&b)), &b);
+  }
+
+  #[test]
+  fn test_se2_between_with_cov() {
+    let a = SE2::new(1.0, 2.0, 0.4);
+    let b = SE2::new(3.0, -1.0, 2.0);
+    let cov_a = vec![0.04, 0.01, 0.0, 0.01, 0.09, 0.005, 0.0, 0.005, 0.01];
+    let cov_b = vec![0.01, 0.0, 0.002, 0.0, 0.02, 0.0, 0.002, 0.0, 0.03];
+    let (d, cov) = a.between_with_cov(&cov_a, &b, &cov_b);
+    let expected = numeric_between_cov::<3>(
+      |da, db| d.inverse().compose(&a.compose(&SE2::exp(da)).between(&b.compose(&SE2::exp(db)))).log(),
+      &cov_a,
+      &cov_b,
+    );
+    assert_close(&cov, &expected, 1e-6);
+  }
+
+  #[test]
+  fn test_se3_between_with_cov() {
+    let a = SE3::from_rotvec([1.0, 2.0, 0.5], [0.3, -0.1, 0.8]);
+    let b = SE3::from_rotvec([-1.0, 0.5, 2.0], [-0.4, 0.6, 1.2]);
+    let mut cov_a = vec![0.0; 36];
+    let mut cov_b = vec![0.0; 36];
+    for i in 0..6 {
+      cov_a[i*6 + i] = 0.01 * (i + 1) as f64;
+      cov_b[i*6 + i] = 0.02;
+    }
+    cov_a[1] = 0.002;
+    cov_a[6] = 0.002;
+    let (d, cov) = a.between_with_cov(&cov_a, &b, &cov_b);
+    let expected = numeric_between_cov::<6>(
+      |da, db| d.inverse().compose(&a.compose(&SE3::exp(da)).between(&b.compose(&SE3::exp(db)))).log(),
+      &cov_a,
+      &cov_b,
+    );
+    assert_close(&cov, &expected, 1e-6);
+  }
+
+  #[test]
+  fn test_so3_log_near_pi() {
+    let n = (1.0f64 + 4.0 + 9.0).sqrt();
+    let axis = [1.0 / n, 2.0 / n, 3.0 / n];
+    for angle in [PI - 1e-3, PI - 1e-9, PI] {
+      let w = [angle * axis[0], angle * axis[1], angle * axis[2]];
+      let _res = so3_log(&so3_exp(&w));
+      assert!((norm3(&_res) - angle).abs() < 1e-9, "{:?}", _res);
+      // at π the axis may flip sign, the rotation is the same
+      assert_close(&quat_to_rotmat(&so3_exp(&_res)), &quat_to_rotmat(&so3_exp(&w)), 1e-9);
+    }
+    // just past π with w < 0 wraps to the short way round
+    let q = so3_exp(&[PI + 1e-3, 0.0, 0.0]);
+    assert_close(&so3_log(&q), &[-(PI - 1e-3), 0.0, 0.0], 1e-9);
+  }
+
+  #[test]
+  fn test_pose3_rotvec_to_euler() {
+    let rpy = [0.1, -0.2, 0.3];
+    let w = so3_log(&euler_to_quat(&rpy));
+    let mut cov = vec![0.0; 36];
+    for i in 0..6 {
+      cov[i*6 + i] = 0.01;
+    }
+    cov[3*6 + 4] = 0.002;
+    cov[4*6 + 3] = 0.002;
+    let z = crate::FullNormal::new(vec![1.0, 2.0, 3.0, w[0], w[1], w[2]], cov.clone()).unwrap();
+    let _res = z.pose3_rotvec_to_euler().unwrap();
+    assert_close(&_res.mu, &[1.0, 2.0, 3.0, rpy[0], rpy[1], rpy[2]], 1e-9);
+    // translation block is unchanged
+    for i in 0..3 {
+      assert_close(&_res.cov[i*6..i*6 + 3], &cov[i*6..i*6 + 3], 1e-12);
+    }
+    // and back again
+    let _res = _res.pose3_euler_to_rotvec().unwrap();
+    assert_close(&_res.mu, &z.mu, 1e-9);
+    assert_close(&_res.cov, &cov, 1e-6);
+
+    assert!(crate::FullNormal::new(vec![0.0; 3], vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap().pose3_rotvec_to_euler().is_err());
+  }
+}
@@ -0,0 +1,227 @@
+
+//! Small dense linear algebra helpers for covariance matrices.
+//!
+//! Matrices are square and stored row-major in a flat `&[f64]` of length `n*n`,
+//! same as the `cov` field of `FullNormal`.
+
+
+/// Returns the dimension `n` if `a` holds an `n x n` matrix.
+pub fn square_dim(
+  a: &[f64]
+) -> Option<usize> {
+  let n = (a.len() as f64).sqrt().round() as usize;
+  if n * n == a.len() {
+    return Some(n);
+  }
+  return None;
+}
+
+/// Returns the first `(row, col)` entry where `a` differs from its transpose by more than `tol`.
+pub fn find_asymmetry(
+  a: &[f64],
+  n: usize,
+  tol: f64,
+) -> Option<(usize, usize)> {
+  for i in 0..n {
+    for j in (i+1)..n {
+      let (aij, aji) = (a[i*n + j], a[j*n + i]);
+      if tol * (1.0 + aij.abs().max(aji.abs())) < (aij - aji).abs() {
+        return Some((i, j));
+      }
+    }
+  }
+  return None;
+}
+
+/// Check that the symmetric matrix `a` is positive semi-definite using an LDL' factorization
+/// which tolerates zero pivots, returns the index of the first diagonal entry where the check fails.
+pub fn find_not_psd(
+  a: &[f64],
+  n: usize,
+  tol: f64,
+) -> Option<usize> {
+  let scale = (0..n).map(|i| a[i*n + i].abs()).fold(1.0, f64::max);
+  let eps = tol * scale;
+  let mut l = vec![0.0; n*n];
+  let mut d = vec![0.0; n];
+  for j in 0..n {
+    let mut dj = a[j*n + j];
+    for k in 0..j {
+      dj -= l[j*n + k] * l[j*n + k] * d[k];
+    }
+    if dj < -eps {
+      return Some(j);
+    }
+    d[j] = dj;
+    for i in (j+1)..n {
+      let mut v = a[i*n + j];
+      for k in 0..j {
+        v -= l[i*n + k] * l[j*n + k] * d[k];
+      }
+      if dj <= eps {
+        // zero pivot, the remaining column must vanish too
+        if eps < v.abs() {
+          return Some(j);
+        }
+        l[i*n + j] = 0.0;
+      } else {
+        l[i*n + j] = v / dj;
+      }
+    }
+  }
+  return None;
+}
+
+/// Returns the transpose of the `n x n` matrix `a`.
+pub fn transpose(
+  a: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  let mut t = vec![0.0; n*n];
+  for i in 0..n {
+    for j in 0..n {
+      t[j*n + i] = a[i*n + j];
+    }
+  }
+  return t;
+}
+
+/// Returns the product `a * b` of two `n x n` matrices.
+pub fn matmul(
+  a: &[f64],
+  b: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  let mut c = vec![0.0; n*n];
+  for i in 0..n {
+    for k in 0..n {
+      let aik = a[i*n + k];
+      for j in 0..n {
+        c[i*n + j] += aik * b[k*n + j];
+      }
+    }
+  }
+  return c;
+}
+
+/// Returns `j * s * j'`, e.g. to propagate a covariance `s` through a Jacobian `j`.
+pub fn congruence(
+  j: &[f64],
+  s: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  return matmul(&matmul(j, s, n), &transpose(j, n), n);
+}
+
+/// Returns the first `(row, col)` entry of `a` which is NaN or infinite.
+pub fn find_not_finite(
+  a: &[f64],
+  n: usize,
+) -> Option<(usize, usize)> {
+  return a.iter().position(|v| !v.is_finite()).map(|k| (k / n, k % n));
+}
+
+/// Cholesky factorization `a = L * L'` of a symmetric positive definite matrix, returns the
+/// row-major lower triangular `L`, or the index of the first diagonal entry where the pivot is not
+/// positive.
+pub fn cholesky(
+  a: &[f64],
+  n: usize,
+) -> Result<Vec<f64>, usize> {
+  let mut l = vec![0.0; n*n];
+  for j in 0..n {
+    let mut d = a[j*n + j];
+    for k in 0..j {
+      d -= l[j*n + k] * l[j*n + k];
+    }
+    if !(0.0 < d) {
+      return Err(j);
+    }
+    let djj = d.sqrt();
+    l[j*n + j] = djj;
+    for i in (j+1)..n {
+      let mut v = a[i*n + j];
+      for k in 0..j {
+        v -= l[i*n + k] * l[j*n + k];
+      }
+      l[i*n + j] = v / djj;
+    }
+  }
+  return Ok(l);
+}
+
+/// Inverse of a symmetric positive definite matrix from its Cholesky factor `l`, see `cholesky`.
+pub fn cholesky_inverse(
+  l: &[f64],
+  n: usize,
+) -> Vec<f64> {
+  // invert the lower triangular factor, then inv(a) = inv(L)' * inv(L)
+  let mut li = vec![0.0; n*n];
+  for j in 0..n {
+    li[j*n + j] = 1.0 / l[j*n + j];
+    for i in (j+1)..n {
+      let mut v = 0.0;
+      for k in j..i {
+        v -= l[i*n + k] * li[k*n + j];
+      }
+      li[i*n + j] = v / l[i*n + i];
+    }
+  }
+  let mut inv = matmul(&transpose(&li, n), &li, n);
+  for i in 0..n {
+    for j in (i+1)..n {
+      let v = 0.5 * (inv[i*n + j] + inv[j*n + i]);
+      inv[i*n + j] = v;
+      inv[j*n + i] = v;
+    }
+  }
+  return inv;
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(
+    a: &[f64],
+    b: &[f64],
+    tol: f64,
+  ) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+      assert!((x - y).abs() <= tol, "{:?} != {:?}", a, b);
+    }
+  }
+
+  #[test]
+  fn test_cholesky() {
+    let a = vec![
+      4.0,  2.0, -2.0,
+      2.0,  10.0, 2.0,
+      -2.0, 2.0,  6.0,
+    ];
+    let l = cholesky(&a, 3).unwrap();
+    assert_close(&l, &[2.0, 0.0, 0.0, 1.0, 3.0, 0.0, -1.0, 1.0, 2.0], 1e-12);
+    assert_close(&matmul(&l, &transpose(&l, 3), 3), &a, 1e-12);
+    assert_close(&matmul(&cholesky_inverse(&l, 3), &a, 3), &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0], 1e-12);
+
+    // singular and indefinite matrices have no Cholesky factor
+    assert_eq!(cholesky(&[1.0, 1.0, 1.0, 1.0], 2), Err(1));
+    assert_eq!(cholesky(&[1.0, 2.0, 2.0, 1.0], 2), Err(1));
+    assert_eq!(cholesky(&[0.0, 0.0, 0.0, 1.0], 2), Err(0));
+  }
+
+  #[test]
+  fn test_find_not_psd() {
+    // positive definite, and semi-definite with zero pivots
+    assert_eq!(find_not_psd(&[4.0, 2.0, 2.0, 10.0], 2, 1e-9), None);
+    assert_eq!(find_not_psd(&[1.0, 1.0, 1.0, 1.0], 2, 1e-9), None);
+    assert_eq!(find_not_psd(&[0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0], 3, 1e-9), None);
+    // indefinite
+    assert_eq!(find_not_psd(&[1.0, 2.0, 2.0, 1.0], 2, 1e-9), Some(1));
+    assert_eq!(find_not_psd(&[-1.0, 0.0, 0.0, 1.0], 2, 1e-9), Some(0));
+    // zero variance with a nonzero correlation
+    assert_eq!(find_not_psd(&[0.0, 0.5, 0.5, 1.0], 2, 1e-9), Some(0));
+  }
+}
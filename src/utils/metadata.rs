@@ -0,0 +1,190 @@
+//! Typed access to the metadata of graph nodes.
+//!
+//! Agents, models, factor graphs, variables, factors and blob entries store metadata as base64
+//! encoded JSON, e.g. "e30=" for `{}`.  `NodeMetadata` decodes this once into a JSON object so
+//! values can be read and written with serde instead of encoding base64 by hand.
+
+
+use std::error::Error;
+
+use base64::{
+  engine::general_purpose,
+  Engine as _,
+};
+use serde::{
+  de::DeserializeOwned,
+  Deserialize,
+  Serialize,
+};
+use serde_json::{
+  Map,
+  Value,
+};
+
+use crate::{
+  Agent,
+  BlobEntry,
+  Factorgraph,
+  FactorDFG,
+  Model,
+  VariableDFG,
+  ValidationError,
+};
+
+
+/// Decoded metadata of a graph node, a JSON object.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeMetadata {
+  pub values: Map<String, Value>,
+}
+
+impl NodeMetadata {
+  pub fn new() -> Self {
+    return Self::default();
+  }
+
+  /// Decode base64 encoded JSON, empty metadata is an empty object.
+  pub fn from_base64(
+    metadata: &str,
+  ) -> Result<Self, Box<dyn Error>> {
+    if metadata.is_empty() {
+      return Ok(Self::new());
+    }
+    let bytes = general_purpose::STANDARD.decode(metadata)?;
+    return match serde_json::from_slice(&bytes)? {
+      Value::Object(values) => Ok(Self { values }),
+      other => Err(Box::new(ValidationError {
+        details: format!("metadata must be a JSON object, not {}", other),
+      })),
+    };
+  }
+
+  pub fn to_base64(&self) -> String {
+    return general_purpose::STANDARD.encode(Value::Object(self.values.clone()).to_string());
+  }
+
+  /// Metadata as stored by the API, i.e. `metadata` itself or the encoded empty object if empty.
+  pub fn base64_or_empty(
+    metadata: &str,
+  ) -> String {
+    if metadata.is_empty() {
+      return Self::new().to_base64();
+    }
+    return metadata.to_owned();
+  }
+
+  /// Value of `key` converted to `T`, `None` if there is no such key.
+  pub fn get<T: DeserializeOwned>(
+    &self,
+    key: &str,
+  ) -> Result<Option<T>, Box<dyn Error>> {
+    return match self.values.get(key) {
+      Some(v) => Ok(Some(serde_json::from_value(v.clone())?)),
+      None => Ok(None),
+    };
+  }
+
+  pub fn set<T: Serialize>(
+    &mut self,
+    key: &str,
+    value: T,
+  ) -> Result<(), Box<dyn Error>> {
+    self.values.insert(key.to_owned(), serde_json::to_value(value)?);
+    return Ok(());
+  }
+
+  pub fn remove(
+    &mut self,
+    key: &str,
+  ) -> Option<Value> {
+    return self.values.remove(key);
+  }
+
+  pub fn contains_key(
+    &self,
+    key: &str,
+  ) -> bool {
+    return self.values.contains_key(key);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    return self.values.is_empty();
+  }
+
+  /// Merge `other` into this metadata, nested objects are merged key by key and any other value
+  /// of `other` replaces the existing one.
+  pub fn merge(
+    &mut self,
+    other: &NodeMetadata,
+  ) {
+    merge_objects(&mut self.values, &other.values);
+  }
+}
+
+fn merge_objects(
+  into: &mut Map<String, Value>,
+  from: &Map<String, Value>,
+) {
+  for (k, v) in from {
+    match (into.get_mut(k), v) {
+      (Some(Value::Object(a)), Value::Object(b)) => merge_objects(a, b),
+      _ => {
+        into.insert(k.clone(), v.clone());
+      },
+    }
+  }
+}
+
+impl From<Map<String, Value>> for NodeMetadata {
+  fn from(values: Map<String, Value>) -> Self {
+    return Self { values };
+  }
+}
+
+
+/// Nodes with base64 encoded JSON metadata.
+pub trait HasMetadata {
+  /// metadata as stored, base64 encoded JSON
+  fn metadata_base64(&self) -> &str;
+  fn set_metadata_base64(&mut self, metadata: String);
+
+  fn get_metadata(&self) -> Result<NodeMetadata, Box<dyn Error>> {
+    return NodeMetadata::from_base64(self.metadata_base64());
+  }
+
+  fn set_metadata(&mut self, metadata: &NodeMetadata) {
+    self.set_metadata_base64(metadata.to_base64());
+  }
+}
+
+// helper macro for nodes with `metadata: String`
+macro_rules! impl_has_metadata {
+  ($T:ty) => {
+    impl HasMetadata for $T {
+      fn metadata_base64(&self) -> &str { &self.metadata }
+      fn set_metadata_base64(&mut self, metadata: String) { self.metadata = metadata; }
+    }
+  }
+}
+
+// helper macro for nodes with `metadata: Option<String>`
+macro_rules! impl_has_optional_metadata {
+  ($T:ty) => {
+    impl HasMetadata for $T {
+      fn metadata_base64(&self) -> &str { self.metadata.as_deref().unwrap_or("") }
+      fn set_metadata_base64(&mut self, metadata: String) { self.metadata = Some(metadata); }
+    }
+  }
+}
+
+impl_has_metadata!(VariableDFG);
+impl_has_metadata!(BlobEntry);
+impl_has_optional_metadata!(Agent);
+impl_has_optional_metadata!(Factorgraph);
+impl_has_optional_metadata!(Model);
+
+impl<F> HasMetadata for FactorDFG<F> {
+  fn metadata_base64(&self) -> &str { self.metadata.as_deref().unwrap_or("") }
+  fn set_metadata_base64(&mut self, metadata: String) { self.metadata = Some(metadata); }
+}